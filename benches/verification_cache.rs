@@ -0,0 +1,33 @@
+use blsful::{Bls12381G2Impl, SecretKey, SignatureSchemes, VerificationCache};
+use std::time::Instant;
+
+const ITERATIONS: u32 = 200;
+
+fn main() {
+    let sk = SecretKey::<Bls12381G2Impl>::new();
+    let pk = sk.public_key();
+    let msg = b"verification cache benchmark";
+    let sig = sk.sign(SignatureSchemes::ProofOfPossession, msg).unwrap();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        sig.verify(&pk, msg).unwrap();
+    }
+    let uncached = start.elapsed();
+
+    let cache = VerificationCache::new(16).unwrap();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        cache.verify(&pk, &sig, msg).unwrap();
+    }
+    let cached = start.elapsed();
+
+    println!(
+        "verify x{ITERATIONS}: uncached {:?} ({:?}/op), cached {:?} ({:?}/op), speedup {:.1}x",
+        uncached,
+        uncached / ITERATIONS,
+        cached,
+        cached / ITERATIONS,
+        uncached.as_secs_f64() / cached.as_secs_f64()
+    );
+}
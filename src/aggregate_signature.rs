@@ -1,25 +1,42 @@
 use crate::impls::inner_types::*;
 use crate::*;
+use std::collections::HashMap;
 
 /// Represents a BLS signature for multiple signatures that signed different messages
-#[derive(PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AggregateSignature<C: BlsSignatureImpl> {
     /// The basic signature scheme
     Basic(
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
     /// The message augmentation signature scheme
     MessageAugmentation(
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
     /// The proof of possession scheme
     ProofOfPossession(
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
 }
@@ -108,7 +125,7 @@ impl_from_derivatives_generic!(AggregateSignature);
 
 impl<C: BlsSignatureImpl> From<&AggregateSignature<C>> for Vec<u8> {
     fn from(value: &AggregateSignature<C>) -> Self {
-        serde_bare::to_vec(value).unwrap()
+        codec::to_vec(value)
     }
 }
 
@@ -116,7 +133,30 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for AggregateSignature<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        serde_bare::from_slice(value).map_err(|e| BlsError::InvalidInputs(e.to_string()))
+        codec::from_slice(value).map_err(|e| BlsError::InvalidInputs(e.to_string()))
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Encode for AggregateSignature<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        let (variant, sig) = match self {
+            Self::Basic(sig) => (0, sig),
+            Self::MessageAugmentation(sig) => (1, sig),
+            Self::ProofOfPossession(sig) => (2, sig),
+        };
+        w.uint(variant);
+        w.point(sig);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for AggregateSignature<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        match r.uint()? {
+            0 => r.point().map(Self::Basic),
+            1 => r.point().map(Self::MessageAugmentation),
+            2 => r.point().map(Self::ProofOfPossession),
+            _ => Err(codec::DecodeError("unknown signature scheme")),
+        }
     }
 }
 
@@ -124,18 +164,280 @@ impl<C: BlsSignatureImpl> AggregateSignature<C> {
     /// Accumulate multiple signatures into a single signature
     /// Verify fails if any signed message is a duplicate
     pub fn from_signatures<B: AsRef<[Signature<C>]>>(signatures: B) -> BlsResult<Self> {
-        Self::try_from(signatures.as_ref())
+        traced!("bls.aggregate", {
+            scheme = ?signatures.as_ref().first().map(Signature::scheme),
+            signatures = signatures.as_ref().len()
+        }, {
+            Self::try_from(signatures.as_ref())
+        })
+    }
+
+    /// Sort `(PublicKey, Signature)` contributions into a canonical order.
+    ///
+    /// Contributions are ordered by the compressed bytes of the public key and
+    /// then the signature so nodes that aggregated the same set produce
+    /// identical contribution records regardless of arrival order
+    pub fn sort_contributions(contributions: &mut [(PublicKey<C>, Signature<C>)]) {
+        contributions.sort_by_cached_key(|(pk, sig)| (Vec::from(pk), Vec::from(sig)));
     }
 
     /// Verify the aggregated signature using the public keys
+    ///
+    /// An empty `data` means no one signed and fails with [`BlsError::EmptyAggregate`]
     pub fn verify<B: AsRef<[u8]>>(&self, data: &[(PublicKey<C>, B)]) -> BlsResult<()> {
-        let ii = data.iter().map(|(pk, m)| (pk.0, m));
+        traced!("bls.aggregate_verify", { scheme = %self.scheme(), messages = data.len() }, {
+            Self::verify_inner(*self.as_raw_value(), self.items(data), Self::hash_item, None)
+        })
+    }
+
+    /// Verify the aggregated signature reporting progress to `progress`.
+    ///
+    /// `progress` is called with `(processed, total)` after each item has been
+    /// hashed into the multi-pairing, so `total` times for a well-formed
+    /// aggregate, followed by the final pairing. It only sees counts and
+    /// can't affect the result, which is the same as [`AggregateSignature::verify`]
+    pub fn verify_with_progress<B, P>(
+        &self,
+        data: &[(PublicKey<C>, B)],
+        mut progress: P,
+    ) -> BlsResult<()>
+    where
+        B: AsRef<[u8]>,
+        P: FnMut(usize, usize),
+    {
+        traced!("bls.aggregate_verify", { scheme = %self.scheme(), messages = data.len() }, {
+            Self::verify_inner(
+                *self.as_raw_value(),
+                self.items(data),
+                Self::hash_item,
+                Some(&mut progress),
+            )
+        })
+    }
+
+    /// Verify an aggregate of signatures created with different schemes.
+    ///
+    /// Each item is a signer's public key, the message it signed and the
+    /// scheme it used. Every message is hashed to curve under its own
+    /// scheme's domain separation tag, with the public key prepended for
+    /// message augmentation, and `sig` is checked against all of them in a
+    /// single multi-pairing. As with [`AggregateSignature::verify`] the
+    /// messages signed with the basic scheme must be distinct from each other.
+    ///
+    /// `sig` is the sum of the individual signatures' raw values since an
+    /// [`AggregateSignature`] can only carry a single scheme.
+    pub fn verify_multi_scheme_aggregate(
+        items: &[(PublicKey<C>, &[u8], SignatureSchemes)],
+        sig: &<C as Pairing>::Signature,
+    ) -> BlsResult<()> {
+        traced!("bls.aggregate_verify", { messages = items.len() }, {
+            let items = items.iter().map(|(pk, msg, scheme)| (pk, *msg, *scheme));
+            Self::verify_inner(*sig, items, Self::hash_item, None)
+        })
+    }
+
+    /// Verify the aggregated signature using a caller supplied mapping from
+    /// messages to the signature group in place of the scheme's hash to curve.
+    ///
+    /// `mapper` is given each message as the scheme would hash it, i.e. with
+    /// the signer's public key prepended for message augmentation, and must be
+    /// the same mapping the signers used. Messages must be distinct when
+    /// [`SignatureSchemes::requires_distinct_messages`] says so.
+    ///
+    /// The security of the signatures rests on `mapper` and is the caller's
+    /// responsibility. It must act like a random oracle into the prime order
+    /// subgroup with domain separation from other protocols; a mapping whose
+    /// outputs have known discrete logarithms, or that collides, lets anyone
+    /// forge signatures
+    pub fn verify_with_mapper<B, M>(&self, data: &[(PublicKey<C>, B)], mapper: M) -> BlsResult<()>
+    where
+        B: AsRef<[u8]>,
+        M: Fn(&[u8]) -> <C as Pairing>::Signature,
+    {
+        traced!("bls.aggregate_verify", { scheme = %self.scheme(), messages = data.len() }, {
+            let hash = |pk: &PublicKey<C>, msg: &[u8], scheme| match scheme {
+                SignatureSchemes::MessageAugmentation => mapper(&pk.augment_message(msg)),
+                SignatureSchemes::Basic | SignatureSchemes::ProofOfPossession => mapper(msg),
+            };
+            Self::verify_inner(*self.as_raw_value(), self.items(data), hash, None)
+        })
+    }
+
+    /// Hash an item's message the way its scheme does when signing
+    fn hash_item(
+        pk: &PublicKey<C>,
+        msg: &[u8],
+        scheme: SignatureSchemes,
+    ) -> <C as Pairing>::Signature {
+        hash_for_scheme::<C>(scheme, pk.0, msg)
+    }
+
+    /// The items of `data` under this aggregate's scheme
+    fn items<'a, B: AsRef<[u8]>>(
+        &self,
+        data: &'a [(PublicKey<C>, B)],
+    ) -> impl ExactSizeIterator<Item = (&'a PublicKey<C>, &'a [u8], SignatureSchemes)> {
+        let scheme = self.scheme();
+        data.iter().map(move |(pk, msg)| (pk, msg.as_ref(), scheme))
+    }
+
+    /// Check `sig` against every `(public key, message, scheme)` item, with
+    /// each message mapped to the signature group by `hash`, in a single
+    /// multi-pairing.
+    ///
+    /// Messages must be distinct from others under the same scheme when
+    /// [`SignatureSchemes::requires_distinct_messages`] says so
+    fn verify_inner<'a>(
+        sig: <C as Pairing>::Signature,
+        items: impl ExactSizeIterator<Item = (&'a PublicKey<C>, &'a [u8], SignatureSchemes)>,
+        hash: impl Fn(&PublicKey<C>, &[u8], SignatureSchemes) -> <C as Pairing>::Signature,
+        mut progress: Option<&mut dyn FnMut(usize, usize)>,
+    ) -> BlsResult<()>
+    where
+        C: 'a,
+    {
+        let total = items.len();
+        if total == 0 {
+            return Err(BlsError::EmptyAggregate);
+        }
+        if sig.is_identity().into() {
+            return Err(BlsError::InvalidSignature);
+        }
+        let mut distinct_msgs = HashMap::new();
+        let mut pairs = Vec::with_capacity(total + 1);
+        for (i, (pk, msg, scheme)) in items.enumerate() {
+            if pk.0.is_identity().into() {
+                return Err(BlsError::InvalidPublicKey);
+            }
+            if scheme.requires_distinct_messages() {
+                if let Some(old) = distinct_msgs.insert((scheme, msg), i) {
+                    return Err(BlsError::InvalidInputs(format!(
+                        "duplicate {} scheme messages detected at {} and {}",
+                        scheme, old, i
+                    )));
+                }
+            }
+            let point = hash(pk, msg, scheme);
+            if point.is_identity().into() {
+                return Err(BlsError::InvalidInputs(format!(
+                    "message at {} maps to the identity point",
+                    i
+                )));
+            }
+            pairs.push((point, pk.0));
+            if let Some(progress) = progress.as_mut() {
+                progress(i + 1, total);
+            }
+        }
+        pairs.push((sig, -<C as Pairing>::PublicKey::generator()));
+        if <C as Pairing>::pairing(&pairs).is_identity().into() {
+            Ok(())
+        } else {
+            Err(BlsError::InvalidSignature)
+        }
+    }
+
+    /// The signature scheme used to create this signature
+    pub fn scheme(&self) -> SignatureSchemes {
         match self {
-            Self::Basic(sig) => <C as BlsSignatureBasic>::aggregate_verify(ii, *sig),
-            Self::MessageAugmentation(sig) => {
-                <C as BlsSignatureMessageAugmentation>::aggregate_verify(ii, *sig)
+            Self::Basic(_) => SignatureSchemes::Basic,
+            Self::MessageAugmentation(_) => SignatureSchemes::MessageAugmentation,
+            Self::ProofOfPossession(_) => SignatureSchemes::ProofOfPossession,
+        }
+    }
+
+    /// Extract the inner raw representation
+    pub fn as_raw_value(&self) -> &<C as Pairing>::Signature {
+        match self {
+            Self::Basic(s) => s,
+            Self::MessageAugmentation(s) => s,
+            Self::ProofOfPossession(s) => s,
+        }
+    }
+
+    /// Remove the signatures accumulated in `other` from this aggregate signature.
+    ///
+    /// `other` must only contain signatures that are also in this aggregate
+    /// otherwise the result will not verify.
+    pub fn subtract(&self, other: &Self) -> BlsResult<Self> {
+        self.combine_with(other, |a, b| a - b)
+    }
+
+    /// Compute the aggregate signature of the signers in `signers` but not in `other_signers`.
+    ///
+    /// `signers` and `other_signers` are bitfields over the same ordered list of signers
+    /// and `other_signers` must be a subset of `signers`.
+    /// Returns the aggregate signature and the bitfield of the signers it contains.
+    pub fn difference(
+        &self,
+        signers: &[bool],
+        other: &Self,
+        other_signers: &[bool],
+    ) -> BlsResult<(Self, Vec<bool>)> {
+        check_signer_bitfields(signers, other_signers)?;
+        if other_signers.iter().zip(signers).any(|(o, s)| *o && !*s) {
+            return Err(BlsError::InvalidInputs(
+                "other signers must be a subset of signers".to_string(),
+            ));
+        }
+        let bitfield = signers
+            .iter()
+            .zip(other_signers)
+            .map(|(s, o)| *s && !*o)
+            .collect();
+        Ok((self.subtract(other)?, bitfield))
+    }
+
+    /// Compute the aggregate signature of the signers in exactly one of `signers`
+    /// or `other_signers`.
+    ///
+    /// The individual signatures can't be recovered from an aggregate so
+    /// `intersection` must be the aggregate of the signers common to both.
+    /// The result is `self + other - 2 * intersection`.
+    /// Returns the aggregate signature and the bitfield of the signers it contains.
+    pub fn symmetric_difference(
+        &self,
+        signers: &[bool],
+        other: &Self,
+        other_signers: &[bool],
+        intersection: &Self,
+    ) -> BlsResult<(Self, Vec<bool>)> {
+        check_signer_bitfields(signers, other_signers)?;
+        let bitfield = signers
+            .iter()
+            .zip(other_signers)
+            .map(|(s, o)| *s ^ *o)
+            .collect();
+        let sig = self
+            .combine_with(other, |a, b| a + b)?
+            .combine_with(intersection, |a, i| a - i.double())?;
+        Ok((sig, bitfield))
+    }
+
+    fn combine_with<F>(&self, other: &Self, f: F) -> BlsResult<Self>
+    where
+        F: Fn(<C as Pairing>::Signature, <C as Pairing>::Signature) -> <C as Pairing>::Signature,
+    {
+        match (self, other) {
+            (Self::Basic(a), Self::Basic(b)) => Ok(Self::Basic(f(*a, *b))),
+            (Self::MessageAugmentation(a), Self::MessageAugmentation(b)) => {
+                Ok(Self::MessageAugmentation(f(*a, *b)))
+            }
+            (Self::ProofOfPossession(a), Self::ProofOfPossession(b)) => {
+                Ok(Self::ProofOfPossession(f(*a, *b)))
             }
-            Self::ProofOfPossession(sig) => <C as BlsSignaturePop>::aggregate_verify(ii, *sig),
+            _ => Err(BlsError::InvalidSignatureScheme),
         }
     }
 }
+
+fn check_signer_bitfields(signers: &[bool], other_signers: &[bool]) -> BlsResult<()> {
+    if signers.len() != other_signers.len() {
+        return Err(BlsError::InvalidInputs(format!(
+            "signer bitfield lengths differ, {} and {}",
+            signers.len(),
+            other_signers.len()
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,431 @@
+//! The binary encoding behind the `Vec<u8>` and `TryFrom<&[u8]>` conversions.
+//!
+//! Values use the [BARE](https://baremessages.org) layout that `serde_bare`
+//! writes for this crate's serde implementations: lengths and enum variants
+//! are LEB128 integers, byte strings and lists are length prefixed, points are
+//! their compressed encoding and scalars are big endian. Shares are the
+//! identifier then the value, each a length prefixed byte string.
+//!
+//! With the `serde` feature [`to_vec`] and [`from_slice`] go through
+//! `serde_bare` so errors keep reporting the format's error. Without it they
+//! use the [`Encode`] and [`Decode`] implementations here, which produce the
+//! same bytes so either build reads what the other wrote.
+use crate::impls::inner_types::*;
+use crate::BlsError;
+use std::fmt::{self, Display, Formatter};
+use vsss_rs::{IdentifierPrimeField, Share, ValueGroup};
+
+/// The error returned when decoding fails
+#[cfg(feature = "serde")]
+pub(crate) use serde_bare::error::Error;
+
+/// The error returned when decoding fails
+#[cfg(not(feature = "serde"))]
+pub(crate) use DecodeError as Error;
+
+/// Encode `value` into a new vector
+#[cfg(feature = "serde")]
+pub(crate) fn to_vec<T: serde::Serialize + ?Sized>(value: &T) -> Vec<u8> {
+    serde_bare::to_vec(value).expect("serializing to a vector can't fail")
+}
+
+/// Encode `value` into a new vector
+#[cfg(not(feature = "serde"))]
+pub(crate) fn to_vec<T: Encode + ?Sized>(value: &T) -> Vec<u8> {
+    encode(value)
+}
+
+/// Decode a value from the start of `bytes`
+#[cfg(feature = "serde")]
+pub(crate) fn from_slice<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    serde_bare::from_slice(bytes)
+}
+
+/// Decode a value from the start of `bytes`
+#[cfg(not(feature = "serde"))]
+pub(crate) fn from_slice<T: Decode>(bytes: &[u8]) -> Result<T, Error> {
+    decode(bytes)
+}
+
+/// Encode `value` with its [`Encode`] implementation regardless of features
+pub(crate) fn encode<T: Encode + ?Sized>(value: &T) -> Vec<u8> {
+    let mut writer = Writer::default();
+    value.encode(&mut writer);
+    writer.into_bytes()
+}
+
+/// Decode a value with its [`Decode`] implementation regardless of features.
+///
+/// Like `serde_bare`, bytes after the value are ignored
+pub(crate) fn decode<T: Decode>(bytes: &[u8]) -> Result<T, DecodeError> {
+    T::decode(&mut Reader::new(bytes))
+}
+
+/// Why a value couldn't be decoded
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct DecodeError(pub(crate) &'static str);
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<DecodeError> for BlsError {
+    fn from(e: DecodeError) -> Self {
+        Self::DeserializationError(e.to_string())
+    }
+}
+
+/// A type that can be written in the BARE layout
+pub(crate) trait Encode {
+    /// Append this value to `w`
+    fn encode(&self, w: &mut Writer);
+}
+
+/// A type that can be read from the BARE layout
+pub(crate) trait Decode: Sized {
+    /// Read a value from `r`
+    fn decode(r: &mut Reader<'_>) -> Result<Self, DecodeError>;
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    fn encode(&self, w: &mut Writer) {
+        (**self).encode(w)
+    }
+}
+
+/// Writes values to a byte vector
+#[derive(Default)]
+pub(crate) struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    /// The bytes written so far
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// A single byte
+    pub fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    /// A little endian `u64`
+    pub fn u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    /// A LEB128 variable length integer
+    pub fn uint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.bytes.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        self.bytes.push(value as u8);
+    }
+
+    /// Bytes with no length prefix
+    pub fn fixed(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// A length prefixed byte string
+    pub fn data(&mut self, bytes: &[u8]) {
+        self.uint(bytes.len() as u64);
+        self.fixed(bytes);
+    }
+
+    /// A length prefixed list
+    pub fn list<T: Encode>(&mut self, items: &[T]) {
+        self.uint(items.len() as u64);
+        for item in items {
+            item.encode(self);
+        }
+    }
+
+    /// A big endian scalar
+    pub fn scalar<F: PrimeField>(&mut self, scalar: &F) {
+        let mut repr = scalar.to_repr();
+        repr.as_mut().reverse();
+        self.fixed(repr.as_ref());
+    }
+
+    /// A compressed point
+    pub fn point<G: GroupEncoding>(&mut self, point: &G) {
+        self.fixed(point.to_bytes().as_ref());
+    }
+
+    /// A share whose value is a scalar
+    pub fn scalar_share<F, S>(&mut self, share: &S)
+    where
+        F: PrimeField,
+        S: Share<Identifier = IdentifierPrimeField<F>, Value = IdentifierPrimeField<F>>,
+    {
+        self.data(share.identifier().0.to_repr().as_ref());
+        self.data(share.value().0.to_repr().as_ref());
+    }
+
+    /// A share whose value is a point
+    pub fn point_share<G, S>(&mut self, share: &S)
+    where
+        G: Group + GroupEncoding + Default,
+        S: Share<Identifier = IdentifierPrimeField<G::Scalar>, Value = ValueGroup<G>>,
+    {
+        self.data(share.identifier().0.to_repr().as_ref());
+        self.data(share.value().0.to_bytes().as_ref());
+    }
+}
+
+/// Reads values from a byte slice
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Read from the start of `bytes`
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// True if every byte has been read
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The next `len` bytes
+    pub fn fixed(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.bytes.len() < len {
+            return Err(DecodeError("unexpected end of input"));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    /// The next byte without reading it
+    pub fn peek(&self) -> Result<u8, DecodeError> {
+        self.bytes
+            .first()
+            .copied()
+            .ok_or(DecodeError("unexpected end of input"))
+    }
+
+    /// A single byte
+    pub fn u8(&mut self) -> Result<u8, DecodeError> {
+        self.fixed(1).map(|b| b[0])
+    }
+
+    /// A little endian `u64`
+    pub fn u64(&mut self) -> Result<u64, DecodeError> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.fixed(8)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    /// A LEB128 variable length integer of at most 10 bytes
+    pub fn uint(&mut self) -> Result<u64, DecodeError> {
+        let mut value = 0u64;
+        for i in 0..10 {
+            let b = self.u8()?;
+            if i == 9 && b > 1 {
+                break;
+            }
+            value |= u64::from(b & 0x7f) << (7 * i);
+            if b < 0x80 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError("invalid variable length integer"))
+    }
+
+    /// A length prefixed byte string
+    pub fn data(&mut self) -> Result<&'a [u8], DecodeError> {
+        let len = self.uint()?;
+        let len = usize::try_from(len).map_err(|_| DecodeError("unexpected end of input"))?;
+        self.fixed(len)
+    }
+
+    /// A length prefixed list
+    pub fn list<T: Decode>(&mut self) -> Result<Vec<T>, DecodeError> {
+        let len = self.uint()?;
+        // Every item is at least a byte so don't trust a longer length
+        let mut items = Vec::with_capacity((len as usize).min(self.bytes.len()));
+        for _ in 0..len {
+            items.push(T::decode(self)?);
+        }
+        Ok(items)
+    }
+
+    /// A big endian scalar
+    pub fn scalar<F: PrimeField>(&mut self) -> Result<F, DecodeError> {
+        let mut repr = F::Repr::default();
+        let len = repr.as_ref().len();
+        repr.as_mut().copy_from_slice(self.fixed(len)?);
+        repr.as_mut().reverse();
+        Option::from(F::from_repr(repr)).ok_or(DecodeError("invalid scalar"))
+    }
+
+    /// A compressed point
+    pub fn point<G: GroupEncoding>(&mut self) -> Result<G, DecodeError> {
+        let mut repr = G::Repr::default();
+        let len = repr.as_ref().len();
+        repr.as_mut().copy_from_slice(self.fixed(len)?);
+        Option::from(G::from_bytes(&repr)).ok_or(DecodeError("invalid point"))
+    }
+
+    /// A share whose value is a scalar
+    pub fn scalar_share<F, S>(&mut self) -> Result<S, DecodeError>
+    where
+        F: PrimeField,
+        S: Share<Identifier = IdentifierPrimeField<F>, Value = IdentifierPrimeField<F>>,
+    {
+        let identifier = Reader::new(self.data()?).scalar_repr()?;
+        let value = Reader::new(self.data()?).scalar_repr()?;
+        Ok(S::with_identifier_and_value(
+            IdentifierPrimeField(identifier),
+            IdentifierPrimeField(value),
+        ))
+    }
+
+    /// A share whose value is a point
+    pub fn point_share<G, S>(&mut self) -> Result<S, DecodeError>
+    where
+        G: Group + GroupEncoding + Default,
+        S: Share<Identifier = IdentifierPrimeField<G::Scalar>, Value = ValueGroup<G>>,
+    {
+        let identifier = Reader::new(self.data()?).scalar_repr()?;
+        let mut value = Reader::new(self.data()?);
+        let point = value.point()?;
+        if !value.is_empty() {
+            return Err(DecodeError("invalid point"));
+        }
+        Ok(S::with_identifier_and_value(
+            IdentifierPrimeField(identifier),
+            ValueGroup(point),
+        ))
+    }
+
+    /// All of the remaining bytes as a scalar's little endian representation
+    fn scalar_repr<F: PrimeField>(&mut self) -> Result<F, DecodeError> {
+        let mut repr = F::Repr::default();
+        if repr.as_ref().len() != self.bytes.len() {
+            return Err(DecodeError("invalid scalar"));
+        }
+        repr.as_mut().copy_from_slice(self.bytes);
+        Option::from(F::from_repr(repr)).ok_or(DecodeError("invalid scalar"))
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::helpers::get_crypto_rng;
+    use crate::*;
+    use serde::{de::DeserializeOwned, Serialize};
+
+    /// Check the codec and `serde_bare` agree on `value` in both directions
+    fn check<T>(value: &T)
+    where
+        T: Encode + Decode + Serialize + DeserializeOwned + PartialEq,
+    {
+        let bytes = encode(value);
+        assert_eq!(bytes, serde_bare::to_vec(value).unwrap());
+        assert!(decode::<T>(&bytes).unwrap() == *value);
+        assert!(serde_bare::from_slice::<T>(&bytes).unwrap() == *value);
+    }
+
+    fn matches_serde_bare<C: BlsSignatureImpl + PartialEq + Serialize + DeserializeOwned>() {
+        const MSG: &[u8] = b"codec matches serde_bare";
+        let sk = SecretKey::<C>::from_hash(b"codec");
+        let pk = sk.public_key();
+        check(&pk);
+
+        let shares = sk.split(2, 3).unwrap();
+        let pk_shares = shares
+            .iter()
+            .map(|s| s.public_key().unwrap())
+            .collect::<Vec<_>>();
+        check(&shares[0]);
+        check(&pk_shares[0]);
+
+        for scheme in [
+            SignatureSchemes::Basic,
+            SignatureSchemes::MessageAugmentation,
+            SignatureSchemes::ProofOfPossession,
+        ] {
+            check(&scheme);
+
+            let sig = sk.sign(scheme, MSG).unwrap();
+            let sig2 = SecretKey::<C>::from_hash(b"codec 2")
+                .sign(scheme, MSG)
+                .unwrap();
+            check(&sig);
+            check(&AggregateSignature::from_signatures([sig, sig2]).unwrap());
+            if scheme == SignatureSchemes::ProofOfPossession {
+                check(&MultiSignature::from_signatures([sig, sig2]).unwrap());
+            }
+
+            let (commitment, x) = ProofCommitment::generate(MSG, sig).unwrap();
+            check(&commitment);
+            let pok = commitment
+                .finalize(x, ProofCommitmentChallenge::<C>::new(), sig)
+                .unwrap();
+            check(&pok);
+            check(&ProofOfKnowledgeTimestamp::generate(MSG, sig).unwrap());
+
+            // Shares can't sign with message augmentation
+            if scheme != SignatureSchemes::MessageAugmentation {
+                let sig_share = shares[0].sign(scheme, MSG).unwrap();
+                check(&sig_share);
+                check(&sig_share.prove_correctness(&shares[0], MSG).unwrap());
+            }
+
+            check(&pk.encrypt_time_lock(scheme, MSG, b"id").unwrap());
+
+            let ciphertext = pk.sign_crypt(scheme, MSG);
+            check(&ciphertext);
+            check(&pk.sign_crypt_with_header(scheme, MSG, b"header"));
+            let decryption_shares = shares
+                .iter()
+                .map(|s| ciphertext.create_decryption_share(s).unwrap())
+                .collect::<Vec<_>>();
+            check(&decryption_shares[0]);
+            check(&SignCryptDecryptionKey::from_shares(&decryption_shares).unwrap());
+        }
+
+        check(&pk.encrypt_key_el_gamal(&sk).unwrap());
+        check(&pk.encrypt_key_el_gamal_with_proof(&sk).unwrap());
+        let el_gamal_shares = pk_shares
+            .iter()
+            .map(|s| ElGamalDecryptionShare::<C>(s.0))
+            .collect::<Vec<_>>();
+        check(&el_gamal_shares[0]);
+        check(&ElGamalDecryptionKey::from_shares(&el_gamal_shares).unwrap());
+
+        let config = ThresholdConfig::new(2, 3).unwrap();
+        check(&sk.split_with_proof(config, get_crypto_rng()).unwrap().1);
+        let dealings = shares[..2]
+            .iter()
+            .map(|s| {
+                let (new_shares, proof) = s.reshare(config).unwrap();
+                (s.public_key().unwrap(), new_shares[0].clone(), proof)
+            })
+            .collect::<Vec<_>>();
+        let (_, rotation_proof) = SecretKeyShare::combine_reshares(config, &dealings).unwrap();
+        check(&rotation_proof);
+    }
+
+    #[test]
+    fn g1_matches_serde_bare() {
+        matches_serde_bare::<Bls12381G1Impl>();
+    }
+
+    #[test]
+    fn g2_matches_serde_bare() {
+        matches_serde_bare::<Bls12381G2Impl>();
+    }
+}
@@ -0,0 +1,246 @@
+use crate::impls::inner_types::*;
+use crate::*;
+use rand_core::{CryptoRng, RngCore};
+use vsss_rs::*;
+
+/// Feldman commitments to the coefficients of a sharing polynomial
+///
+/// The first commitment is the group public key so anyone can confirm the
+/// dealing is for the claimed key with [`DealerProof::verify`] and each
+/// recipient can check their share with [`DealerProof::verify_share`] or
+/// [`DealerProof::verify_feldman`] before participating. This is returned by
+/// every operation that deals shares: [`SecretKey::split_with_proof`],
+/// [`SecretKey::deal_additive`], [`SecretKeyShare::combine_dealings`] and,
+/// inside a [`RotationProof`], [`SecretKeyShare::reshare`].
+#[derive(Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct DealerProof<C: BlsSignatureImpl> {
+    /// The commitments to the sharing polynomial coefficients
+    pub commitments: Vec<PublicKey<C>>,
+}
+
+impl<C: BlsSignatureImpl> fmt::Debug for DealerProof<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "DealerProof{{ commitments: {:?} }}", self.commitments)
+    }
+}
+
+impl<C: BlsSignatureImpl> Clone for DealerProof<C> {
+    fn clone(&self) -> Self {
+        Self {
+            commitments: self.commitments.clone(),
+        }
+    }
+}
+
+impl_from_derivatives_generic!(DealerProof);
+
+impl<C: BlsSignatureImpl> From<&DealerProof<C>> for Vec<u8> {
+    fn from(value: &DealerProof<C>) -> Self {
+        codec::to_vec(value)
+    }
+}
+
+impl<C: BlsSignatureImpl> TryFrom<&[u8]> for DealerProof<C> {
+    type Error = BlsError;
+
+    fn try_from(value: &[u8]) -> BlsResult<Self> {
+        let output = codec::from_slice(value)?;
+        Ok(output)
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Encode for DealerProof<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.list(&self.commitments);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for DealerProof<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        Ok(Self {
+            commitments: r.list()?,
+        })
+    }
+}
+
+impl<C: BlsSignatureImpl> DealerProof<C> {
+    /// Commit to the polynomial with `coefficients`, constant term first
+    pub(crate) fn commit(coefficients: &[<<C as Pairing>::PublicKey as Group>::Scalar]) -> Self {
+        let generator = <C as Pairing>::PublicKey::generator();
+        Self {
+            commitments: coefficients
+                .iter()
+                .map(|c| PublicKey(generator * c))
+                .collect(),
+        }
+    }
+
+    /// The group public key committed to by this proof
+    pub fn group_public_key(&self) -> BlsResult<PublicKey<C>> {
+        self.commitments
+            .first()
+            .copied()
+            .ok_or_else(|| BlsError::InvalidInputs("dealer proof is empty".to_string()))
+    }
+
+    /// The number of shares required to combine the dealt shares
+    pub fn threshold(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// Verify the committed polynomial is at `group_pub` at zero.
+    ///
+    /// This only binds the commitment vector to the key: anyone can build
+    /// commitments that pass by putting `group_pub` first. Use
+    /// [`DealerProof::verify_share`] on the dealt shares to check they lie on
+    /// the committed polynomial
+    pub fn verify(&self, group_pub: &PublicKey<C>) -> BlsResult<()> {
+        if self.commitments.len() < 2 {
+            return Err(BlsError::InvalidInputs(
+                "dealer proof has fewer than 2 commitments".to_string(),
+            ));
+        }
+        if self
+            .commitments
+            .iter()
+            .any(|c| bool::from(c.0.is_identity()))
+        {
+            return Err(BlsError::InvalidProof);
+        }
+        if self.commitments[0].0 != group_pub.0 {
+            return Err(BlsError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Verify a public key share lies on the committed polynomial
+    pub fn verify_share(&self, share: &PublicKeyShare<C>) -> BlsResult<()> {
+        if bool::from(share.0.identifier().0.is_zero()) {
+            return Err(BlsError::InvalidInputs(
+                "share identifier cannot be zero".to_string(),
+            ));
+        }
+        if !bool::from(share.verify_single(&self.commitments)) {
+            return Err(BlsError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    /// Verify a recipient's secret key share lies on the committed polynomial
+    pub fn verify_feldman(&self, share: &SecretKeyShare<C>) -> BlsResult<()> {
+        self.verify_share(&share.public_key()?)
+    }
+}
+
+impl<C: BlsSignatureImpl> SecretKey<C> {
+    /// Secret share this key like [`SecretKey::split_with_rng`] and also
+    /// return a [`DealerProof`] committing to the sharing polynomial
+    pub fn split_with_proof(
+        &self,
+        config: ThresholdConfig,
+        rng: impl RngCore + CryptoRng,
+    ) -> BlsResult<(Vec<SecretKeyShare<C>>, DealerProof<C>)> {
+        let (threshold, limit) = (config.threshold(), config.limit());
+        if self.is_zero().into() {
+            return Err(BlsError::InvalidSecretKey);
+        }
+
+        let coefficients = random_poly(self.0, threshold, rng);
+        let shares = (1..=limit)
+            .map(|j| {
+                let x = <<C as Pairing>::PublicKey as Group>::Scalar::from(j as u64);
+                SecretKeyShare(<C as Pairing>::SecretKeyShare::with_identifier_and_value(
+                    IdentifierPrimeField(x),
+                    IdentifierPrimeField(eval_poly(&coefficients, x)),
+                ))
+            })
+            .collect();
+
+        Ok((shares, DealerProof::commit(&coefficients)))
+    }
+}
+
+impl<C: BlsSignatureImpl> SecretKey<C> {
+    /// Deal this key, one party's additive share of a larger secret, to a
+    /// new committee as `threshold`-of-`limit` Shamir shares.
+    ///
+    /// Every holder of an additive share deals only their own share, so no
+    /// party ever sees another's share or the secret. Each recipient checks
+    /// and sums the dealings sent to them with
+    /// [`SecretKeyShare::combine_dealings`] to get their share of the summed
+    /// secret.
+    pub fn deal_additive(
+        &self,
+        config: ThresholdConfig,
+    ) -> BlsResult<(Vec<SecretKeyShare<C>>, DealerProof<C>)> {
+        self.deal_additive_with_rng(config, get_crypto_rng())
+    }
+
+    /// Deal this additive share using a specified RNG
+    pub fn deal_additive_with_rng(
+        &self,
+        config: ThresholdConfig,
+        rng: impl RngCore + CryptoRng,
+    ) -> BlsResult<(Vec<SecretKeyShare<C>>, DealerProof<C>)> {
+        self.split_with_proof(config, rng)
+    }
+}
+
+impl<C: BlsSignatureImpl> SecretKeyShare<C> {
+    /// Combine the dealings this party received from every additive share
+    /// holder into its Shamir share of the summed secret.
+    ///
+    /// Each dealing is the share dealt to this party by
+    /// [`SecretKey::deal_additive`] and the dealer's [`DealerProof`]. Every
+    /// share is checked with [`DealerProof::verify_feldman`] before it's used.
+    /// The returned proof is the sum of the dealers' commitments, so its first
+    /// commitment is the group public key and every party that combines the
+    /// same dealings gets the same proof.
+    pub fn combine_dealings(
+        dealings: &[(SecretKeyShare<C>, DealerProof<C>)],
+    ) -> BlsResult<(SecretKeyShare<C>, DealerProof<C>)> {
+        let (first_share, first_proof) = dealings
+            .first()
+            .ok_or_else(|| BlsError::InvalidInputs("no dealings provided".to_string()))?;
+        let identifier = *first_share.0.identifier();
+        let threshold = first_proof.threshold();
+        if threshold < 2 {
+            return Err(BlsError::InvalidInputs(
+                "dealer proof has fewer than 2 commitments".to_string(),
+            ));
+        }
+
+        let mut value = <<C as Pairing>::PublicKey as Group>::Scalar::ZERO;
+        let mut commitments = vec![<C as Pairing>::PublicKey::identity(); threshold];
+        for (i, (share, proof)) in dealings.iter().enumerate() {
+            if proof.threshold() != threshold {
+                return Err(BlsError::InvalidInputs(format!(
+                    "dealing at {} has a different threshold",
+                    i
+                )));
+            }
+            if *share.0.identifier() != identifier {
+                return Err(BlsError::InvalidInputs(format!(
+                    "dealing at {} is for a different share identifier",
+                    i
+                )));
+            }
+            proof.verify_feldman(share)?;
+            value += share.0.value().0;
+            for (sum, c) in commitments.iter_mut().zip(&proof.commitments) {
+                *sum += c.0;
+            }
+        }
+
+        let share = SecretKeyShare(<C as Pairing>::SecretKeyShare::with_identifier_and_value(
+            identifier,
+            IdentifierPrimeField(value),
+        ));
+        let proof = DealerProof {
+            commitments: commitments.into_iter().map(PublicKey).collect(),
+        };
+        Ok((share, proof))
+    }
+}
@@ -2,15 +2,26 @@ use crate::*;
 use core::ops::{Add, AddAssign};
 
 /// An ElGamal ciphertext
-#[derive(Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ElGamalCiphertext<C: BlsSignatureImpl> {
     /// The first component of the ciphertext
-    #[serde(serialize_with = "traits::public_key::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::public_key::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::public_key::serialize::<C, _>",
+            deserialize_with = "traits::public_key::deserialize::<C, _>"
+        )
+    )]
     pub c1: <C as Pairing>::PublicKey,
     /// The second component of the ciphertext
-    #[serde(serialize_with = "traits::public_key::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::public_key::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::public_key::serialize::<C, _>",
+            deserialize_with = "traits::public_key::deserialize::<C, _>"
+        )
+    )]
     pub c2: <C as Pairing>::PublicKey,
 }
 
@@ -47,7 +58,7 @@ impl<C: BlsSignatureImpl> subtle::ConditionallySelectable for ElGamalCiphertext<
     }
 }
 
-impl<'a, 'b, C: BlsSignatureImpl> Add<&'b ElGamalCiphertext<C>> for &'a ElGamalCiphertext<C> {
+impl<'b, C: BlsSignatureImpl> Add<&'b ElGamalCiphertext<C>> for &ElGamalCiphertext<C> {
     type Output = ElGamalCiphertext<C>;
 
     fn add(self, rhs: &'b ElGamalCiphertext<C>) -> Self::Output {
@@ -63,7 +74,7 @@ impl<'a, C: BlsSignatureImpl> Add<&'a ElGamalCiphertext<C>> for ElGamalCiphertex
     }
 }
 
-impl<'a, C: BlsSignatureImpl> Add<ElGamalCiphertext<C>> for &'a ElGamalCiphertext<C> {
+impl<C: BlsSignatureImpl> Add<ElGamalCiphertext<C>> for &ElGamalCiphertext<C> {
     type Output = ElGamalCiphertext<C>;
 
     fn add(self, rhs: ElGamalCiphertext<C>) -> Self::Output {
@@ -98,7 +109,7 @@ impl<'a, C: BlsSignatureImpl> AddAssign<&'a ElGamalCiphertext<C>> for ElGamalCip
 
 impl<C: BlsSignatureImpl> From<&ElGamalCiphertext<C>> for Vec<u8> {
     fn from(value: &ElGamalCiphertext<C>) -> Self {
-        serde_bare::to_vec(value).expect("failed to serialize ElGamalCiphertext")
+        codec::to_vec(value)
     }
 }
 
@@ -106,11 +117,27 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for ElGamalCiphertext<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        let ciphertext = serde_bare::from_slice(value)?;
+        let ciphertext = codec::from_slice(value)?;
         Ok(ciphertext)
     }
 }
 
+impl<C: BlsSignatureImpl> codec::Encode for ElGamalCiphertext<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.point(&self.c1);
+        w.point(&self.c2);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for ElGamalCiphertext<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        Ok(Self {
+            c1: r.point()?,
+            c2: r.point()?,
+        })
+    }
+}
+
 impl_from_derivatives_generic!(ElGamalCiphertext);
 
 impl<C: BlsSignatureImpl> ElGamalCiphertext<C> {
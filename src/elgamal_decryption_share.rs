@@ -3,10 +3,16 @@ use crate::*;
 /// A public key share is a point on the curve
 /// Must be combined with other public key shares
 /// in order to decrypt a ciphertext
-#[derive(PartialEq, Eq, Serialize, Deserialize)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ElGamalDecryptionShare<C: BlsSignatureImpl>(
-    #[serde(serialize_with = "traits::public_key_share::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::public_key_share::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::public_key_share::serialize::<C, _>",
+            deserialize_with = "traits::public_key_share::deserialize::<C, _>"
+        )
+    )]
     pub <C as Pairing>::PublicKeyShare,
 );
 
@@ -26,7 +32,7 @@ impl<C: BlsSignatureImpl> ElGamalDecryptionShare<C> {}
 
 impl<C: BlsSignatureImpl> From<&ElGamalDecryptionShare<C>> for Vec<u8> {
     fn from(value: &ElGamalDecryptionShare<C>) -> Self {
-        serde_bare::to_vec(value).expect("failed to serialize ElGamalDecryptionShare")
+        codec::to_vec(value)
     }
 }
 
@@ -34,19 +40,37 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for ElGamalDecryptionShare<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        let share = serde_bare::from_slice(value)?;
+        let share = codec::from_slice(value)?;
         Ok(share)
     }
 }
 
+impl<C: BlsSignatureImpl> codec::Encode for ElGamalDecryptionShare<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.point_share(&self.0);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for ElGamalDecryptionShare<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        r.point_share().map(Self)
+    }
+}
+
 impl_from_derivatives_generic!(ElGamalDecryptionShare);
 
 /// An ElGamal decryption key where the secret key is hidden or combined from shares
 /// that can decrypt ciphertext
-#[derive(Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ElGamalDecryptionKey<C: BlsSignatureImpl>(
-    #[serde(serialize_with = "traits::public_key::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::public_key::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::public_key::serialize::<C, _>",
+            deserialize_with = "traits::public_key::deserialize::<C, _>"
+        )
+    )]
     pub <C as Pairing>::PublicKey,
 );
 
@@ -58,7 +82,7 @@ impl<C: BlsSignatureImpl> Clone for ElGamalDecryptionKey<C> {
 
 impl<C: BlsSignatureImpl> From<&ElGamalDecryptionKey<C>> for Vec<u8> {
     fn from(value: &ElGamalDecryptionKey<C>) -> Self {
-        serde_bare::to_vec(value).expect("failed to serialize ElGamalDecryptionKey")
+        codec::to_vec(value)
     }
 }
 
@@ -66,11 +90,23 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for ElGamalDecryptionKey<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        let key = serde_bare::from_slice(value)?;
+        let key = codec::from_slice(value)?;
         Ok(key)
     }
 }
 
+impl<C: BlsSignatureImpl> codec::Encode for ElGamalDecryptionKey<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.point(&self.0);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for ElGamalDecryptionKey<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        r.point().map(Self)
+    }
+}
+
 impl_from_derivatives_generic!(ElGamalDecryptionKey);
 
 impl<C: BlsSignatureImpl> ElGamalDecryptionKey<C> {
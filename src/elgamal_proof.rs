@@ -2,25 +2,44 @@ use crate::impls::inner_types::*;
 use crate::*;
 
 /// A Discrete Log Proof tied to a specific ElGamal ciphertext
-#[derive(Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ElGamalProof<C: BlsSignatureImpl> {
     /// The el-gamal ciphertext
-    #[serde(bound(
-        serialize = "ElGamalCiphertext<C>: Serialize",
-        deserialize = "ElGamalCiphertext<C>: Deserialize<'de>"
-    ))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(bound(
+            serialize = "ElGamalCiphertext<C>: Serialize",
+            deserialize = "ElGamalCiphertext<C>: Deserialize<'de>"
+        ))
+    )]
     pub ciphertext: ElGamalCiphertext<C>,
     /// The proof of encrypted message
-    #[serde(serialize_with = "traits::scalar::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::scalar::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::scalar::serialize::<C, _>",
+            deserialize_with = "traits::scalar::deserialize::<C, _>"
+        )
+    )]
     pub message_proof: <<C as Pairing>::PublicKey as Group>::Scalar,
     /// The proof of the blinder
-    #[serde(serialize_with = "traits::scalar::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::scalar::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::scalar::serialize::<C, _>",
+            deserialize_with = "traits::scalar::deserialize::<C, _>"
+        )
+    )]
     pub blinder_proof: <<C as Pairing>::PublicKey as Group>::Scalar,
     /// The fiat-shamir heuristic challenge
-    #[serde(serialize_with = "traits::scalar::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::scalar::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::scalar::serialize::<C, _>",
+            deserialize_with = "traits::scalar::deserialize::<C, _>"
+        )
+    )]
     pub challenge: <<C as Pairing>::PublicKey as Group>::Scalar,
 }
 
@@ -54,7 +73,7 @@ impl<C: BlsSignatureImpl> Clone for ElGamalProof<C> {
 
 impl<C: BlsSignatureImpl> From<&ElGamalProof<C>> for Vec<u8> {
     fn from(value: &ElGamalProof<C>) -> Self {
-        serde_bare::to_vec(value).expect("Failed to serialize ElGamalProof")
+        codec::to_vec(value)
     }
 }
 
@@ -62,11 +81,31 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for ElGamalProof<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> BlsResult<Self> {
-        let proof = serde_bare::from_slice(value)?;
+        let proof = codec::from_slice(value)?;
         Ok(proof)
     }
 }
 
+impl<C: BlsSignatureImpl> codec::Encode for ElGamalProof<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        self.ciphertext.encode(w);
+        w.scalar(&self.message_proof);
+        w.scalar(&self.blinder_proof);
+        w.scalar(&self.challenge);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for ElGamalProof<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        Ok(Self {
+            ciphertext: ElGamalCiphertext::decode(r)?,
+            message_proof: r.scalar()?,
+            blinder_proof: r.scalar()?,
+            challenge: r.scalar()?,
+        })
+    }
+}
+
 impl_from_derivatives_generic!(ElGamalProof);
 
 impl<C: BlsSignatureImpl> ElGamalProof<C> {
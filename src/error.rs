@@ -1,32 +1,138 @@
-use thiserror::Error;
+use std::fmt::{self, Display, Formatter};
+#[cfg(feature = "serde")]
+use std::sync::Arc;
 
 /// The error types generated by this library
-#[derive(Error, Clone, Debug)]
+#[derive(Clone, Debug)]
 pub enum BlsError {
     /// Invalid signing operation
-    #[error("invalid signing operation: {0}")]
     SigningError(String),
     /// Invalid inputs to a function
-    #[error("invalid inputs: {0}")]
     InvalidInputs(String),
     /// An invalid signature error
-    #[error("invalid signature")]
     InvalidSignature,
+    /// An invalid public key error
+    InvalidPublicKey,
+    /// An invalid secret key error, e.g. the secret key is zero
+    InvalidSecretKey,
     /// The proof was invalid
-    #[error("invalid proof")]
     InvalidProof,
     /// The signature schemes don't match
-    #[error("Invalid signature scheme")]
     InvalidSignatureScheme,
     /// The decryption share is invalid
-    #[error("Invalid sign cryption share")]
     InvalidDecryptionShare,
+    /// An aggregate was verified against no signers
+    EmptyAggregate,
+    /// Fewer shares were supplied than the threshold requires
+    InsufficientShares {
+        /// The number of shares required
+        threshold: usize,
+        /// The number of shares supplied
+        received: usize,
+    },
     /// A verifiable secret sharing scheme error
-    #[error("an error occurred during secret sharing")]
     VsssError,
+    /// The encoded data uses a format version this implementation can't read
+    UnsupportedVersion(u8),
     /// An error occurred during serialization
-    #[error("serialization error: {0}")]
     DeserializationError(String),
+    /// The binary serialization format failed to decode the data
+    ///
+    /// The format's error is returned by [`std::error::Error::source`]
+    #[cfg(feature = "serde")]
+    SerializationFormat(Arc<serde_bare::error::Error>),
+    /// An operation failed with the underlying error
+    Context {
+        /// The name of the operation that failed
+        operation: &'static str,
+        /// The underlying error
+        source: Box<BlsError>,
+    },
+}
+
+impl Display for BlsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SigningError(e) => write!(f, "invalid signing operation: {}", e),
+            Self::InvalidInputs(e) => write!(f, "invalid inputs: {}", e),
+            Self::InvalidSignature => write!(f, "invalid signature"),
+            Self::InvalidPublicKey => write!(f, "invalid public key"),
+            Self::InvalidSecretKey => write!(f, "invalid secret key"),
+            Self::InvalidProof => write!(f, "invalid proof"),
+            Self::InvalidSignatureScheme => write!(f, "Invalid signature scheme"),
+            Self::InvalidDecryptionShare => write!(f, "Invalid sign cryption share"),
+            Self::EmptyAggregate => write!(f, "aggregate has no signers"),
+            Self::InsufficientShares {
+                threshold,
+                received,
+            } => write!(
+                f,
+                "insufficient shares: expected at least {}, received {}",
+                threshold, received
+            ),
+            Self::VsssError => write!(f, "an error occurred during secret sharing"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported version: {}", v),
+            Self::DeserializationError(e) => write!(f, "serialization error: {}", e),
+            #[cfg(feature = "serde")]
+            Self::SerializationFormat(e) => write!(f, "serialization error: {}", e),
+            Self::Context { operation, source } => write!(f, "{} failed: {}", operation, source),
+        }
+    }
+}
+
+impl std::error::Error for BlsError {
+    /// The wrapped error for [`BlsError::Context`] or the format's error for
+    /// `BlsError::SerializationFormat`
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "serde")]
+            Self::SerializationFormat(e) => Some(e.as_ref()),
+            Self::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl BlsError {
+    /// Wrap this error with the name of the operation that failed
+    pub fn context(self, operation: &'static str) -> Self {
+        Self::Context {
+            operation,
+            source: Box::new(self),
+        }
+    }
+
+    /// The name of the outermost operation that failed if one was attached
+    pub fn operation(&self) -> Option<&'static str> {
+        match self {
+            Self::Context { operation, .. } => Some(operation),
+            _ => None,
+        }
+    }
+
+    /// The innermost error with every operation context removed
+    ///
+    /// Match on this instead of the error itself to check the underlying
+    /// variant regardless of how many operations wrapped it.
+    pub fn root(&self) -> &BlsError {
+        let mut err = self;
+        while let Self::Context { source, .. } = err {
+            err = source;
+        }
+        err
+    }
+}
+
+/// Attach the name of the failed operation to an error result
+pub trait ErrorContext<T> {
+    /// Wrap the error, if any, with the name of the operation that failed
+    fn context(self, operation: &'static str) -> BlsResult<T>;
+}
+
+impl<T> ErrorContext<T> for BlsResult<T> {
+    fn context(self, operation: &'static str) -> BlsResult<T> {
+        self.map_err(|e| e.context(operation))
+    }
 }
 
 /// The result type generated by this library
@@ -38,8 +144,9 @@ impl From<vsss_rs::Error> for BlsError {
     }
 }
 
+#[cfg(feature = "serde")]
 impl From<serde_bare::error::Error> for BlsError {
     fn from(e: serde_bare::error::Error) -> Self {
-        Self::DeserializationError(e.to_string())
+        Self::SerializationFormat(Arc::new(e))
     }
 }
@@ -1,8 +1,12 @@
 use crate::impls::inner_types::*;
-use crate::{BlsSignatureImpl, Pairing};
+use crate::{
+    BlsSignatureBasic, BlsSignatureImpl, BlsSignatureMessageAugmentation, BlsSignaturePop,
+    HashToPoint, Pairing, PublicKey, SignatureSchemes,
+};
+use core::ops::Deref;
 use rand_chacha::ChaCha20Rng;
-use rand_core::SeedableRng;
-use subtle::{Choice, CtOption};
+use rand_core::{CryptoRng, RngCore, SeedableRng};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
 
 pub const KEYGEN_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
 
@@ -34,10 +38,146 @@ pub fn byte_xor(arr1: &[u8], arr2: &[u8]) -> Vec<u8> {
     o
 }
 
+/// Combine a domain separator and struct hash into the message signed for
+/// structured data, i.e. `0x19 0x01 || domain_separator || struct_hash`
+pub fn structured_message(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 66] {
+    let mut msg = [0u8; 66];
+    msg[..2].copy_from_slice(&[0x19, 0x01]);
+    msg[2..34].copy_from_slice(domain_separator);
+    msg[34..].copy_from_slice(struct_hash);
+    msg
+}
+
+/// The tag prepended to a precomputed digest so signing it can't be
+/// confused with signing the same 32 bytes as a raw message
+pub const DIGEST_MESSAGE_PREFIX: &[u8; 13] = b"BLS_DIGEST32_";
+
+/// The message signed for a precomputed digest, i.e. `DIGEST_MESSAGE_PREFIX || digest`
+pub fn digest_message(digest: &[u8; 32]) -> [u8; 45] {
+    let mut msg = [0u8; 45];
+    msg[..13].copy_from_slice(DIGEST_MESSAGE_PREFIX);
+    msg[13..].copy_from_slice(digest);
+    msg
+}
+
+/// The coefficients of a secret sharing polynomial, constant term first,
+/// which are overwritten with zero when dropped
+pub struct SecretPoly<F: Field>(Vec<F>);
+
+impl<F: Field> Deref for SecretPoly<F> {
+    type Target = [F];
+
+    fn deref(&self) -> &[F] {
+        &self.0
+    }
+}
+
+impl<F: Field> Drop for SecretPoly<F> {
+    fn drop(&mut self) {
+        for c in self.0.iter_mut() {
+            *c = F::ZERO;
+        }
+        // Keep the writes from being removed as dead stores
+        core::hint::black_box(&mut self.0);
+    }
+}
+
+/// A polynomial of degree `threshold - 1` with `constant` as its constant
+/// term and random coefficients otherwise
+pub fn random_poly<F: Field>(
+    constant: F,
+    threshold: usize,
+    mut rng: impl RngCore + CryptoRng,
+) -> SecretPoly<F> {
+    let mut coefficients = Vec::with_capacity(threshold);
+    coefficients.push(constant);
+    for _ in 1..threshold {
+        coefficients.push(F::random(&mut rng));
+    }
+    SecretPoly(coefficients)
+}
+
+/// Evaluate the polynomial with `coefficients`, constant term first, at `x`
+pub fn eval_poly<F: Field>(coefficients: &[F], x: F) -> F {
+    coefficients
+        .iter()
+        .rev()
+        .fold(F::ZERO, |value, c| value * x + c)
+}
+
+/// The multiples `j·16ⁱ·P`, `j` in `0..16`, of a fixed point `P` so
+/// multiplying it by many scalars only takes one addition per scalar nibble
+///
+/// Entries are picked with a constant time scan of the whole row so the
+/// scalar doesn't leak through which entry was read. Scalars are read as
+/// their little endian representation which both backends use
+pub struct FixedBaseTable<G: Group>(Vec<[G; 16]>);
+
+impl<G: Group + ConditionallySelectable> FixedBaseTable<G> {
+    /// Precompute the table for `point`
+    pub fn new(point: G) -> Self {
+        let rows = (G::Scalar::NUM_BITS as usize).div_ceil(4);
+        let mut table = Vec::with_capacity(rows);
+        let mut base = point;
+        for _ in 0..rows {
+            let mut row = [G::identity(); 16];
+            for j in 1..16 {
+                row[j] = row[j - 1] + base;
+            }
+            base = row[15] + base;
+            table.push(row);
+        }
+        Self(table)
+    }
+
+    /// Compute `P·scalar`
+    pub fn mul(&self, scalar: &G::Scalar) -> G {
+        let repr = scalar.to_repr();
+        let bytes = repr.as_ref();
+        let mut result = G::identity();
+        for (i, row) in self.0.iter().enumerate() {
+            let nibble = (bytes[i / 2] >> ((i % 2) * 4)) & 0x0f;
+            let mut entry = G::identity();
+            for (j, p) in row.iter().enumerate() {
+                entry.conditional_assign(p, (j as u8).ct_eq(&nibble));
+            }
+            result += entry;
+        }
+        result
+    }
+}
+
+/// Hash `msg` to the signature group the way `scheme` does for the signer
+/// with public key `pk`, i.e. with `pk` prepended for message augmentation
+pub(crate) fn hash_for_scheme<C: BlsSignatureImpl>(
+    scheme: SignatureSchemes,
+    pk: <C as Pairing>::PublicKey,
+    msg: &[u8],
+) -> <C as Pairing>::Signature {
+    match scheme {
+        SignatureSchemes::Basic => {
+            <C as HashToPoint>::hash_to_point(msg, <C as BlsSignatureBasic>::DST)
+        }
+        SignatureSchemes::MessageAugmentation => <C as HashToPoint>::hash_to_point(
+            PublicKey::<C>(pk).augment_message(msg),
+            <C as BlsSignatureMessageAugmentation>::DST,
+        ),
+        SignatureSchemes::ProofOfPossession => {
+            <C as HashToPoint>::hash_to_point(msg, <C as BlsSignaturePop>::SIG_DST)
+        }
+    }
+}
+
 pub fn get_crypto_rng() -> ChaCha20Rng {
     ChaCha20Rng::from_entropy()
 }
 
+/// Call `f` so `?` in a traced body returns from the body rather than the caller
+#[cfg(feature = "tracing")]
+pub fn run_traced<T>(f: impl FnOnce() -> crate::BlsResult<T>) -> crate::BlsResult<T> {
+    f()
+}
+
 pub fn pairing_g1_g2(points: &[(G1Projective, G2Projective)]) -> Gt {
     let t = points
         .iter()
@@ -111,59 +251,6 @@ pub fn scalar_from_le_bytes<C: BlsSignatureImpl, const N: usize>(
     <<C as Pairing>::PublicKey as Group>::Scalar::from_repr(repr)
 }
 
-pub mod fixed_arr {
-    use core::fmt::{self, Formatter};
-    use serde::{
-        de::{self, SeqAccess, Visitor},
-        Deserialize, Deserializer,
-    };
-
-    pub trait BigArray<'de>: Sized {
-        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>;
-    }
-
-    impl<'de, const N: usize> BigArray<'de> for [u8; N] {
-        fn deserialize<D>(d: D) -> Result<Self, D::Error>
-        where
-            D: Deserializer<'de>,
-        {
-            if d.is_human_readable() {
-                let hex_str = <&str>::deserialize(d)?;
-                let mut share = [0u8; N];
-                hex::decode_to_slice(hex_str, &mut share).map_err(de::Error::custom)?;
-                return Ok(share);
-            }
-
-            struct ArrayVisitor<const N: usize>;
-
-            impl<'de, const N: usize> Visitor<'de> for ArrayVisitor<N> {
-                type Value = [u8; N];
-
-                fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
-                    write!(formatter, "an array of length {}", N)
-                }
-
-                fn visit_seq<A>(self, mut seq: A) -> Result<[u8; N], A::Error>
-                where
-                    A: SeqAccess<'de>,
-                {
-                    let mut arr = [0u8; N];
-                    for (i, b) in arr.iter_mut().enumerate() {
-                        *b = seq
-                            .next_element()?
-                            .ok_or_else(|| de::Error::invalid_length(i, &self))?;
-                    }
-                    Ok(arr)
-                }
-            }
-
-            d.deserialize_tuple(N, ArrayVisitor)
-        }
-    }
-}
-
 pub trait IsZero {
     fn is_zero(&self) -> Choice;
 }
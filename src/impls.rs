@@ -13,17 +13,19 @@ use core::{
     marker::PhantomData,
     str::FromStr,
 };
-use rand::Rng;
 use rand_core::{CryptoRng, RngCore};
 
 /// Types that implement BLS signatures
 pub trait BlsSignatureImpl:
     BlsSignatureBasic + BlsSignatureMessageAugmentation + BlsSignaturePop
 {
+    /// The name of the implementation used to tag serialized values
+    const NAME: &'static str;
 }
 
 /// A BLS signature implementation
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BlsSignature<T: BlsSignatureImpl>(PhantomData<T>);
 
 impl Default for BlsSignature<Bls12381G1Impl> {
@@ -52,11 +54,8 @@ impl<T: BlsSignatureImpl> BlsSignature<T> {
     }
 
     /// Compute a secret key from a CS-PRNG
-    pub fn random_secret_key(mut rng: impl RngCore + CryptoRng) -> SecretKey<T> {
-        SecretKey(<T as HashToScalar>::hash_to_scalar(
-            rng.gen::<[u8; SECRET_KEY_BYTES]>(),
-            KEYGEN_SALT,
-        ))
+    pub fn random_secret_key(rng: impl RngCore + CryptoRng) -> SecretKey<T> {
+        SecretKey::random(rng)
     }
 
     /// Create a new random commitment challenge for signature proofs of knowledge
@@ -158,6 +157,7 @@ impl FromStr for Bls12381 {
     }
 }
 
+#[cfg(feature = "serde")]
 impl Serialize for Bls12381 {
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
         if s.is_human_readable() {
@@ -168,6 +168,7 @@ impl Serialize for Bls12381 {
     }
 }
 
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for Bls12381 {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
         if d.is_human_readable() {
@@ -1,14 +1,14 @@
 use crate::impls::inner_types::*;
 use crate::*;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use vsss_rs::*;
 
 /// Represents BLS signatures on the BLS12-381 curve where
 /// Signatures are in G1 and Public Keys are in G2 or
 /// i.e. signatures are small and public keys are large
-#[derive(
-    Copy, Clone, Debug, Default, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize,
-)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Bls12381G1Impl;
 
 impl HashToPoint for Bls12381G1Impl {
@@ -40,6 +40,7 @@ impl Pairing for Bls12381G1Impl {
     }
 }
 
+#[cfg(feature = "serde")]
 impl BlsSerde for Bls12381G1Impl {
     fn serialize_scalar<S: Serializer>(scalar: &Scalar, serializer: S) -> Result<S::Ok, S::Error> {
         <Scalar as Serialize>::serialize(scalar, serializer)
@@ -49,7 +50,7 @@ impl BlsSerde for Bls12381G1Impl {
         share: &Self::SecretKeyShare,
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
-        share.serialize(serializer)
+        share::serialize_scalar(share, serializer)
     }
 
     fn serialize_signature<S: Serializer>(
@@ -82,7 +83,7 @@ impl BlsSerde for Bls12381G1Impl {
     fn deserialize_scalar_share<'de, D: Deserializer<'de>>(
         deserializer: D,
     ) -> Result<Self::SecretKeyShare, D::Error> {
-        Self::SecretKeyShare::deserialize(deserializer)
+        share::deserialize_scalar(deserializer)
     }
 
     fn deserialize_signature<'de, D: Deserializer<'de>>(
@@ -138,10 +139,13 @@ impl BlsMultiKey for Bls12381G1Impl {}
 
 impl BlsMultiSignature for Bls12381G1Impl {}
 
-impl BlsSignatureImpl for Bls12381G1Impl {}
+impl BlsSignatureImpl for Bls12381G1Impl {
+    const NAME: &'static str = "bls12_381_g1";
+}
 
 /// The BLS12381 G1 hash to public key group
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Bls12381G1Hasher;
 
 impl HashToPoint for Bls12381G1Hasher {
@@ -4,9 +4,8 @@ use crate::*;
 /// Represents BLS signatures on the BLS12-381 curve where
 /// Signatures are in G2 and Public Keys are in G1 or
 /// i.e. signatures are large and public keys are small
-#[derive(
-    Copy, Clone, Debug, Default, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize,
-)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Bls12381G2Impl;
 
 impl HashToPoint for Bls12381G2Impl {
@@ -38,6 +37,7 @@ impl Pairing for Bls12381G2Impl {
     }
 }
 
+#[cfg(feature = "serde")]
 impl BlsSerde for Bls12381G2Impl {
     fn serialize_scalar<S: Serializer>(scalar: &Scalar, serializer: S) -> Result<S::Ok, S::Error> {
         <Scalar as Serialize>::serialize(scalar, serializer)
@@ -47,7 +47,7 @@ impl BlsSerde for Bls12381G2Impl {
         share: &Self::SecretKeyShare,
         serializer: S,
     ) -> Result<S::Ok, S::Error> {
-        share.serialize(serializer)
+        share::serialize_scalar(share, serializer)
     }
 
     fn serialize_signature<S: Serializer>(
@@ -80,7 +80,7 @@ impl BlsSerde for Bls12381G2Impl {
     fn deserialize_scalar_share<'de, D: Deserializer<'de>>(
         deserializer: D,
     ) -> Result<Self::SecretKeyShare, D::Error> {
-        Self::SecretKeyShare::deserialize(deserializer)
+        share::deserialize_scalar(deserializer)
     }
 
     fn deserialize_signature<'de, D: Deserializer<'de>>(
@@ -136,10 +136,13 @@ impl BlsMultiKey for Bls12381G2Impl {}
 
 impl BlsMultiSignature for Bls12381G2Impl {}
 
-impl BlsSignatureImpl for Bls12381G2Impl {}
+impl BlsSignatureImpl for Bls12381G2Impl {
+    const NAME: &'static str = "bls12_381_g2";
+}
 
 /// The BLS12381 G1 hash to public key group
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Bls12381G2Hasher;
 
 impl HashToPoint for Bls12381G2Hasher {
@@ -0,0 +1,75 @@
+use crate::*;
+
+/// The cost parameters for deriving key material from a low-entropy passphrase
+///
+/// Derivation is only reproducible with the same parameters so they should be
+/// stored alongside anything derived with them.
+///
+/// Deserializing goes through [`KdfParams::new`] so stored parameters can't
+/// weaken the iteration count below the minimum.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "KdfParamsRepr"))]
+pub struct KdfParams {
+    /// The number of PBKDF2-HMAC-SHA512 iterations
+    iterations: u32,
+}
+
+/// The serialized form of [`KdfParams`] before it's checked
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct KdfParamsRepr {
+    iterations: u32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<KdfParamsRepr> for KdfParams {
+    type Error = BlsError;
+
+    fn try_from(value: KdfParamsRepr) -> BlsResult<Self> {
+        Self::new(value.iterations)
+    }
+}
+
+impl Default for KdfParams {
+    /// The 2048 iterations used by BIP39
+    fn default() -> Self {
+        Self {
+            iterations: Self::BIP39_ITERATIONS,
+        }
+    }
+}
+
+impl KdfParams {
+    /// The iteration count used by BIP39 to derive a seed from a mnemonic
+    pub const BIP39_ITERATIONS: u32 = 2048;
+
+    /// Create new parameters with the given iteration count.
+    ///
+    /// The count cannot be less than [`KdfParams::BIP39_ITERATIONS`]
+    pub fn new(iterations: u32) -> BlsResult<Self> {
+        if iterations < Self::BIP39_ITERATIONS {
+            return Err(BlsError::InvalidInputs(format!(
+                "iterations cannot be less than {}",
+                Self::BIP39_ITERATIONS
+            )));
+        }
+        Ok(Self { iterations })
+    }
+
+    /// The number of PBKDF2-HMAC-SHA512 iterations
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Stretch `passphrase` with `salt` into a 64 byte seed
+    pub(crate) fn derive_seed(
+        &self,
+        passphrase: &[u8],
+        salt: &[u8],
+    ) -> zeroize::Zeroizing<[u8; 64]> {
+        let mut seed = zeroize::Zeroizing::new([0u8; 64]);
+        pbkdf2::pbkdf2_hmac::<sha2::Sha512>(passphrase, salt, self.iterations, seed.as_mut());
+        seed
+    }
+}
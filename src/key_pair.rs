@@ -0,0 +1,96 @@
+use crate::*;
+use rand_core::{CryptoRng, RngCore};
+
+/// A secret key and its public key
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyPair<C: BlsSignatureImpl> {
+    secret_key: SecretKey<C>,
+    public_key: PublicKey<C>,
+}
+
+impl<C: BlsSignatureImpl> Default for KeyPair<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: BlsSignatureImpl> TryFrom<SecretKey<C>> for KeyPair<C> {
+    type Error = BlsError;
+
+    fn try_from(secret_key: SecretKey<C>) -> BlsResult<Self> {
+        if secret_key.is_zero().into() {
+            return Err(BlsError::InvalidSecretKey);
+        }
+        let public_key = secret_key.public_key();
+        Ok(Self {
+            secret_key,
+            public_key,
+        })
+    }
+}
+
+impl<C: BlsSignatureImpl> KeyPair<C> {
+    /// Create a new random key pair
+    pub fn new() -> Self {
+        Self::random(get_crypto_rng())
+    }
+
+    /// Create a new random key pair from a CS-PRNG
+    pub fn random(rng: impl RngCore + CryptoRng) -> Self {
+        let secret_key = SecretKey::random(rng);
+        let public_key = secret_key.public_key();
+        Self {
+            secret_key,
+            public_key,
+        }
+    }
+
+    /// The secret key
+    pub fn secret_key(&self) -> &SecretKey<C> {
+        &self.secret_key
+    }
+
+    /// The public key
+    pub fn public_key(&self) -> &PublicKey<C> {
+        &self.public_key
+    }
+
+    /// The prefix of the message signed by a rotation certificate so it can't
+    /// be confused with a signature over the raw public key bytes
+    pub const ROTATION_PREFIX: &'static [u8] = b"BLS_KEY_ROTATION_";
+
+    /// Generate the next key pair and a rotation certificate linking it to this one.
+    ///
+    /// The certificate is this secret key signing [`KeyPair::ROTATION_PREFIX`]
+    /// followed by the new public key bytes with the proof of possession scheme.
+    /// Check it with [`KeyPair::verify_rotation`]
+    pub fn rotate(&self, rng: impl RngCore + CryptoRng) -> (KeyPair<C>, Signature<C>) {
+        let next = Self::random(rng);
+        let certificate = self
+            .secret_key
+            .sign(
+                SignatureSchemes::ProofOfPossession,
+                &Self::rotation_message(&next.public_key),
+            )
+            .expect("key pair secret keys are never zero");
+        (next, certificate)
+    }
+
+    /// Check `certificate` shows the holder of `old` rotated to `new`
+    pub fn verify_rotation(
+        old: &PublicKey<C>,
+        new: &PublicKey<C>,
+        certificate: &Signature<C>,
+    ) -> BlsResult<()> {
+        if certificate.scheme() != SignatureSchemes::ProofOfPossession {
+            return Err(BlsError::InvalidSignatureScheme);
+        }
+        certificate.verify(old, Self::rotation_message(new))
+    }
+
+    fn rotation_message(new: &PublicKey<C>) -> Vec<u8> {
+        let mut msg = Self::ROTATION_PREFIX.to_vec();
+        msg.extend_from_slice(&Vec::from(new));
+        msg
+    }
+}
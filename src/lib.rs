@@ -4,7 +4,7 @@
 //!
 //! Since BLS signatures can use either G1 or G2 fields, there are two types of
 //! public keys and signatures.
-#![deny(unsafe_code)]
+#![forbid(unsafe_code)]
 #![warn(
     missing_docs,
     trivial_casts,
@@ -18,16 +18,20 @@ compile_error!("At least `rust` or `blst` must be selected");
 
 #[macro_use]
 mod macros;
+mod codec;
 mod helpers;
 
 use helpers::*;
 
 mod aggregate_signature;
+mod dealer_proof;
 mod elgamal_ciphertext;
 mod elgamal_decryption_share;
 mod elgamal_proof;
 mod error;
 mod impls;
+mod kdf_params;
+mod key_pair;
 mod multi_public_key;
 mod multi_signature;
 mod proof_commitment;
@@ -35,23 +39,35 @@ mod proof_of_knowledge;
 mod proof_of_possession;
 mod public_key;
 mod public_key_share;
+mod rotation_proof;
 mod secret_key;
 mod secret_key_share;
+mod share_correctness_proof;
 mod sig_types;
 mod sign_crypt_ciphertext;
 mod sign_decryption_share;
 mod signature;
 mod signature_share;
+mod signature_slice_reader;
+#[cfg(feature = "serde")]
+mod tagged;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod threshold_config;
 mod time_crypt_ciphertext;
 mod traits;
+mod verification_cache;
 
 pub use error::*;
 pub use impls::*;
 
 pub use aggregate_signature::*;
+pub use dealer_proof::*;
 pub use elgamal_ciphertext::*;
 pub use elgamal_decryption_share::*;
 pub use elgamal_proof::*;
+pub use kdf_params::*;
+pub use key_pair::*;
 pub use multi_public_key::*;
 pub use multi_signature::*;
 pub use proof_commitment::*;
@@ -59,19 +75,27 @@ pub use proof_of_knowledge::*;
 pub use proof_of_possession::*;
 pub use public_key::*;
 pub use public_key_share::*;
+pub use rotation_proof::*;
 pub use secret_key::*;
 pub use secret_key_share::*;
+pub use share_correctness_proof::*;
 pub use sig_types::*;
 pub use sign_crypt_ciphertext::*;
 pub use sign_decryption_share::*;
 pub use signature::*;
 pub use signature_share::*;
+pub use signature_slice_reader::*;
+#[cfg(feature = "serde")]
+pub use tagged::*;
+pub use threshold_config::*;
 pub use time_crypt_ciphertext::*;
 pub use traits::*;
+pub use verification_cache::*;
 
 pub use vsss_rs;
 
 use inner_types::*;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     fmt::{self, Display, Formatter, LowerHex, UpperHex},
@@ -82,14 +106,26 @@ use vsss_rs::{DefaultShare, IdentifierPrimeField, Share, ValueGroup};
 use zeroize::DefaultIsZeroes;
 
 /// The share type for points in G1
-#[derive(
-    Copy, Clone, Debug, Default, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize,
-)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Ord, PartialOrd, Hash)]
 #[repr(transparent)]
 pub struct InnerPointShareG1(
     pub DefaultShare<IdentifierPrimeField<Scalar>, ValueGroup<G1Projective>>,
 );
 
+#[cfg(feature = "serde")]
+impl Serialize for InnerPointShareG1 {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        share::serialize_point(&self.0, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for InnerPointShareG1 {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        share::deserialize_point(d).map(Self)
+    }
+}
+
 impl subtle::ConditionallySelectable for InnerPointShareG1 {
     fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
         let identifier1 = a.0.identifier.0;
@@ -204,13 +240,12 @@ impl Share for InnerPointShareG1 {
 impl InnerPointShareG1 {
     /// Convert secret share from InnerPointShareG1 v1 to the newer v2 format
     pub fn from_v1_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
-        #[derive(Deserialize)]
-        struct V1(#[serde(deserialize_with = "fixed_arr::BigArray::deserialize")] [u8; 49]);
-        let v1 = serde_bare::from_slice::<V1>(bytes)
+        let v1 = codec::Reader::new(bytes)
+            .fixed(49)
             .map_err(|e| BlsError::InvalidInputs(e.to_string()))?;
-        let identifier = Scalar::from(v1.0[0] as u64);
+        let identifier = Scalar::from(v1[0] as u64);
         let mut repr = [0u8; 48];
-        repr.as_mut().copy_from_slice(&v1.0[1..]);
+        repr.as_mut().copy_from_slice(&v1[1..]);
         let value = Option::from(G1Projective::from_compressed(&repr)).ok_or_else(|| {
             BlsError::InvalidInputs("Invalid compressed G1Projective".to_string())
         })?;
@@ -219,14 +254,26 @@ impl InnerPointShareG1 {
 }
 
 /// The share type for points in G2
-#[derive(
-    Copy, Clone, Debug, Default, PartialEq, Eq, Ord, PartialOrd, Hash, Serialize, Deserialize,
-)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Ord, PartialOrd, Hash)]
 #[repr(transparent)]
 pub struct InnerPointShareG2(
     pub DefaultShare<IdentifierPrimeField<Scalar>, ValueGroup<G2Projective>>,
 );
 
+#[cfg(feature = "serde")]
+impl Serialize for InnerPointShareG2 {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        share::serialize_point(&self.0, s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for InnerPointShareG2 {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        share::deserialize_point(d).map(Self)
+    }
+}
+
 impl DefaultIsZeroes for InnerPointShareG2 {}
 
 impl subtle::ConditionallySelectable for InnerPointShareG2 {
@@ -340,13 +387,12 @@ impl Share for InnerPointShareG2 {
 impl InnerPointShareG2 {
     /// Convert secret share from InnerPointShareG1 v1 to the newer v2 format
     pub fn from_v1_bytes(bytes: &[u8]) -> Result<Self, BlsError> {
-        #[derive(Deserialize)]
-        struct V1(#[serde(deserialize_with = "fixed_arr::BigArray::deserialize")] [u8; 97]);
-        let v1 = serde_bare::from_slice::<V1>(bytes)
+        let v1 = codec::Reader::new(bytes)
+            .fixed(97)
             .map_err(|e| BlsError::InvalidInputs(e.to_string()))?;
-        let identifier = Scalar::from(v1.0[0] as u64);
+        let identifier = Scalar::from(v1[0] as u64);
         let mut repr = [0u8; 96];
-        repr.as_mut().copy_from_slice(&v1.0[1..]);
+        repr.as_mut().copy_from_slice(&v1[1..]);
         let value = Option::from(G2Projective::from_compressed(&repr)).ok_or_else(|| {
             BlsError::InvalidInputs("Invalid compressed G1Projective".to_string())
         })?;
@@ -65,3 +65,31 @@ macro_rules! impl_from_derivatives {
         }
     };
 }
+
+/// Run `$body` inside a debug span named `$name` with the given fields and
+/// record whether it returned `Ok` or `Err` in the `outcome` field.
+///
+/// Without the `tracing` feature this expands to `$body` alone.
+#[cfg(feature = "tracing")]
+macro_rules! traced {
+    ($name:literal, { $($fields:tt)* }, $body:block) => {{
+        let span = tracing::debug_span!(
+            $name,
+            $($fields)*,
+            outcome = tracing::field::Empty
+        )
+        .entered();
+        let result = crate::helpers::run_traced(|| $body);
+        let outcome = if result.is_ok() { "ok" } else { "error" };
+        span.record("outcome", outcome);
+        tracing::debug!(outcome);
+        result
+    }};
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! traced {
+    ($name:literal, { $($fields:tt)* }, $body:block) => {
+        $body
+    };
+}
@@ -2,11 +2,17 @@ use crate::impls::inner_types::*;
 use crate::*;
 
 /// An accumulated public key
-#[derive(Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct MultiPublicKey<C: BlsSignatureImpl>(
     /// The inner raw value
-    #[serde(serialize_with = "traits::public_key::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::public_key::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::public_key::serialize::<C, _>",
+            deserialize_with = "traits::public_key::deserialize::<C, _>"
+        )
+    )]
     pub <C as Pairing>::PublicKey,
 );
 
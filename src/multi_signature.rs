@@ -2,24 +2,40 @@ use crate::impls::inner_types::*;
 use crate::*;
 
 /// Represents a BLS signature for multiple signatures that signed different messages
-#[derive(PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MultiSignature<C: BlsSignatureImpl> {
     /// The basic signature scheme
     Basic(
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
     /// The message augmentation signature scheme
     MessageAugmentation(
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
     /// The proof of possession scheme
     ProofOfPossession(
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
 }
@@ -110,7 +126,7 @@ impl_from_derivatives_generic!(MultiSignature);
 
 impl<C: BlsSignatureImpl> From<&MultiSignature<C>> for Vec<u8> {
     fn from(value: &MultiSignature<C>) -> Self {
-        serde_bare::to_vec(value).unwrap()
+        codec::to_vec(value)
     }
 }
 
@@ -118,7 +134,30 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for MultiSignature<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        serde_bare::from_slice(value).map_err(|_| BlsError::InvalidSignature)
+        codec::from_slice(value).map_err(|_| BlsError::InvalidSignature)
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Encode for MultiSignature<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        let (variant, sig) = match self {
+            Self::Basic(sig) => (0, sig),
+            Self::MessageAugmentation(sig) => (1, sig),
+            Self::ProofOfPossession(sig) => (2, sig),
+        };
+        w.uint(variant);
+        w.point(sig);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for MultiSignature<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        match r.uint()? {
+            0 => r.point().map(Self::Basic),
+            1 => r.point().map(Self::MessageAugmentation),
+            2 => r.point().map(Self::ProofOfPossession),
+            _ => Err(codec::DecodeError("unknown signature scheme")),
+        }
     }
 }
 
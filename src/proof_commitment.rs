@@ -5,27 +5,43 @@ use rand_core::{CryptoRng, RngCore};
 use subtle::CtOption;
 
 /// The commitment portion of the signature proof of knowledge
-#[derive(PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ProofCommitment<C: BlsSignatureImpl> {
     /// The basic signature scheme
     Basic(
         /// The commitment
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
     /// The message augmentation signature scheme
     MessageAugmentation(
         /// The commitment
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
     /// The proof of possession signature scheme
     ProofOfPossession(
         /// The commitment
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
 }
@@ -87,7 +103,7 @@ impl_from_derivatives_generic!(ProofCommitment);
 
 impl<C: BlsSignatureImpl> From<&ProofCommitment<C>> for Vec<u8> {
     fn from(value: &ProofCommitment<C>) -> Self {
-        serde_bare::to_vec(value).unwrap()
+        codec::to_vec(value)
     }
 }
 
@@ -103,7 +119,30 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for ProofCommitment<C> {
                 value.len()
             )));
         }
-        serde_bare::from_slice(value).map_err(|e| BlsError::InvalidInputs(e.to_string()))
+        codec::from_slice(value).map_err(|e| BlsError::InvalidInputs(e.to_string()))
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Encode for ProofCommitment<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        let (variant, sig) = match self {
+            Self::Basic(sig) => (0, sig),
+            Self::MessageAugmentation(sig) => (1, sig),
+            Self::ProofOfPossession(sig) => (2, sig),
+        };
+        w.uint(variant);
+        w.point(sig);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for ProofCommitment<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        match r.uint()? {
+            0 => r.point().map(Self::Basic),
+            1 => r.point().map(Self::MessageAugmentation),
+            2 => r.point().map(Self::ProofOfPossession),
+            _ => Err(codec::DecodeError("unknown signature scheme")),
+        }
     }
 }
 
@@ -166,11 +205,17 @@ impl<C: BlsSignatureImpl> ProofCommitment<C> {
 }
 
 /// A commitment secret used to create the proof of knowledge
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProofCommitmentSecret<C: BlsSignatureImpl>(
     /// The commitment secret raw value
-    #[serde(serialize_with = "traits::scalar::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::scalar::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::scalar::serialize::<C, _>",
+            deserialize_with = "traits::scalar::deserialize::<C, _>"
+        )
+    )]
     pub <<C as Pairing>::PublicKey as Group>::Scalar,
 );
 
@@ -218,11 +263,17 @@ impl<C: BlsSignatureImpl> ProofCommitmentSecret<C> {
 
 /// The proof of knowledge challenge value generated by the server in
 /// step 2 of the proof generation process
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProofCommitmentChallenge<C: BlsSignatureImpl>(
     /// The commitment challenge raw value
-    #[serde(serialize_with = "traits::scalar::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::scalar::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::scalar::serialize::<C, _>",
+            deserialize_with = "traits::scalar::deserialize::<C, _>"
+        )
+    )]
     pub <<C as Pairing>::PublicKey as Group>::Scalar,
 );
 
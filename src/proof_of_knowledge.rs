@@ -2,39 +2,70 @@ use crate::*;
 use subtle::Choice;
 
 /// A signature proof of knowledge
-#[derive(PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ProofOfKnowledge<C: BlsSignatureImpl> {
     /// The basic signature scheme
     Basic {
         /// The commitment value
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         u: <C as Pairing>::Signature,
         /// The proof
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         v: <C as Pairing>::Signature,
     },
     /// The message augmentation signature scheme
     MessageAugmentation {
         /// The commitment value
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         u: <C as Pairing>::Signature,
         /// The proof
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         v: <C as Pairing>::Signature,
     },
     /// The proof of possession signature scheme
     ProofOfPossession {
         /// The commitment value
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         u: <C as Pairing>::Signature,
         /// The proof
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         v: <C as Pairing>::Signature,
     },
 }
@@ -112,7 +143,7 @@ impl<C: BlsSignatureImpl> subtle::ConditionallySelectable for ProofOfKnowledge<C
 
 impl<C: BlsSignatureImpl> From<&ProofOfKnowledge<C>> for Vec<u8> {
     fn from(value: &ProofOfKnowledge<C>) -> Self {
-        serde_bare::to_vec(value).expect("Failed to serialize ProofOfKnowledge")
+        codec::to_vec(value)
     }
 }
 
@@ -120,11 +151,37 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for ProofOfKnowledge<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> BlsResult<Self> {
-        let output = serde_bare::from_slice(value)?;
+        let output = codec::from_slice(value)?;
         Ok(output)
     }
 }
 
+impl<C: BlsSignatureImpl> codec::Encode for ProofOfKnowledge<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        let (variant, u, v) = match self {
+            Self::Basic { u, v } => (0, u, v),
+            Self::MessageAugmentation { u, v } => (1, u, v),
+            Self::ProofOfPossession { u, v } => (2, u, v),
+        };
+        w.uint(variant);
+        w.point(u);
+        w.point(v);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for ProofOfKnowledge<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        let variant = r.uint()?;
+        let (u, v) = (r.point()?, r.point()?);
+        match variant {
+            0 => Ok(Self::Basic { u, v }),
+            1 => Ok(Self::MessageAugmentation { u, v }),
+            2 => Ok(Self::ProofOfPossession { u, v }),
+            _ => Err(codec::DecodeError("unknown signature scheme")),
+        }
+    }
+}
+
 impl_from_derivatives_generic!(ProofOfKnowledge);
 
 impl<C: BlsSignatureImpl> ProofOfKnowledge<C> {
@@ -165,13 +222,17 @@ impl<C: BlsSignatureImpl> ProofOfKnowledge<C> {
 }
 
 /// A signature proof of knowledge based on a timestamp
-#[derive(PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProofOfKnowledgeTimestamp<C: BlsSignatureImpl> {
     /// The inner proof of knowledge
-    #[serde(bound(
-        serialize = "ProofOfKnowledge<C>: serde::Serialize",
-        deserialize = "ProofOfKnowledge<C>: serde::Deserialize<'de>"
-    ))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(bound(
+            serialize = "ProofOfKnowledge<C>: serde::Serialize",
+            deserialize = "ProofOfKnowledge<C>: serde::Deserialize<'de>"
+        ))
+    )]
     pub proof: ProofOfKnowledge<C>,
     /// The timestamp associated with the proof
     pub timestamp: u64,
@@ -228,7 +289,7 @@ impl<C: BlsSignatureImpl> subtle::ConditionallySelectable for ProofOfKnowledgeTi
 
 impl<C: BlsSignatureImpl> From<&ProofOfKnowledgeTimestamp<C>> for Vec<u8> {
     fn from(value: &ProofOfKnowledgeTimestamp<C>) -> Self {
-        serde_bare::to_vec(value).expect("Failed to serialize ProofOfKnowledgeTimestamp")
+        codec::to_vec(value)
     }
 }
 
@@ -236,11 +297,27 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for ProofOfKnowledgeTimestamp<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> BlsResult<Self> {
-        let output = serde_bare::from_slice(value)?;
+        let output = codec::from_slice(value)?;
         Ok(output)
     }
 }
 
+impl<C: BlsSignatureImpl> codec::Encode for ProofOfKnowledgeTimestamp<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        self.proof.encode(w);
+        w.u64(self.timestamp);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for ProofOfKnowledgeTimestamp<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        Ok(Self {
+            proof: ProofOfKnowledge::decode(r)?,
+            timestamp: r.u64()?,
+        })
+    }
+}
+
 impl_from_derivatives_generic!(ProofOfKnowledgeTimestamp);
 
 impl<C: BlsSignatureImpl> ProofOfKnowledgeTimestamp<C> {
@@ -2,11 +2,17 @@ use crate::*;
 use subtle::{Choice, ConditionallySelectable};
 
 /// A proof of possession of the secret key
-#[derive(PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ProofOfPossession<C: BlsSignatureImpl>(
     /// The BLS proof of possession raw value
-    #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::signature::serialize::<C, _>",
+            deserialize_with = "traits::signature::deserialize::<C, _>"
+        )
+    )]
     pub <C as Pairing>::Signature,
 );
 
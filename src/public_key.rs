@@ -1,12 +1,19 @@
 use crate::impls::inner_types::*;
 use crate::*;
+use rand_core::{CryptoRng, RngCore};
 
 /// A BLS public key
-#[derive(Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PublicKey<C: BlsSignatureImpl>(
     /// The BLS public key raw value
-    #[serde(serialize_with = "traits::public_key::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::public_key::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::public_key::serialize::<C, _>",
+            deserialize_with = "traits::public_key::deserialize::<C, _>"
+        )
+    )]
     pub <C as Pairing>::PublicKey,
 );
 
@@ -74,8 +81,86 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for PublicKey<C> {
     }
 }
 
+impl<C: BlsSignatureImpl> codec::Encode for PublicKey<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.point(&self.0);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for PublicKey<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        r.point().map(Self)
+    }
+}
+
 impl<C: BlsSignatureImpl> PublicKey<C> {
+    /// Convert a compressed public key without checking it is in the prime order subgroup.
+    ///
+    /// This is intended for loading many keys at once that are then checked
+    /// with [`PublicKey::batch_validate`] before use.
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> BlsResult<Self> {
+        let mut repr = C::PublicKey::default().to_bytes();
+        let len = repr.as_ref().len();
+
+        if len != bytes.len() {
+            return Err(BlsError::InvalidInputs(format!(
+                "Invalid length, expected {}, got {}",
+                len,
+                bytes.len()
+            )));
+        }
+
+        repr.as_mut().copy_from_slice(bytes);
+        let key: Option<C::PublicKey> = C::PublicKey::from_bytes_unchecked(&repr).into();
+        key.map(Self)
+            .ok_or_else(|| BlsError::InvalidInputs("Invalid byte sequence".to_string()))
+    }
+
+    /// Check every key is not the identity and is in the prime order subgroup.
+    ///
+    /// Checking a single random linear combination of the keys is not sound
+    /// for BLS12-381 since the cofactors have small prime factors, e.g. the G1
+    /// cofactor is divisible by 3, so a combination hides an invalid key with
+    /// probability up to 1/3. Each key is checked instead and the error
+    /// reports the index of the first invalid key.
+    pub fn batch_validate(keys: &[PublicKey<C>]) -> BlsResult<()> {
+        for (i, key) in keys.iter().enumerate() {
+            let valid =
+                !key.0.is_identity() & C::PublicKey::from_bytes(&key.0.to_bytes()).is_some();
+            if !bool::from(valid) {
+                return Err(BlsError::InvalidInputs(format!(
+                    "public key at index {} is invalid",
+                    i
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Find the first of several candidate signatures that is valid for `msg`
+    /// under this public key, e.g. when deduplicating redundant submissions.
+    ///
+    /// Candidates not created with `scheme` are skipped. Returns the index of
+    /// the valid signature in `sigs` or `None` if none verify
+    pub fn verify_any_signature<B: AsRef<[u8]>>(
+        &self,
+        msg: B,
+        sigs: &[Signature<C>],
+        scheme: SignatureSchemes,
+    ) -> Option<usize> {
+        let msg = msg.as_ref();
+        sigs.iter()
+            .position(|sig| sig.scheme() == scheme && sig.verify(self, msg).is_ok())
+    }
+
     /// Encrypt a message using signcryption
+    ///
+    /// The sender doesn't need to know whether this is a single key or a
+    /// threshold group key, e.g. one from [`PublicKey::from_shares`]. A group
+    /// decrypts cooperatively: each member creates a decryption share with
+    /// [`SignCryptCiphertext::create_decryption_share`], which others can check
+    /// with [`SignDecryptionShare::verify`], and any threshold of shares opens
+    /// the ciphertext with [`SignCryptCiphertext::decrypt_with_shares`]
     pub fn sign_crypt<B: AsRef<[u8]>>(
         &self,
         scheme: SignatureSchemes,
@@ -87,7 +172,144 @@ impl<C: BlsSignatureImpl> PublicKey<C> {
             SignatureSchemes::ProofOfPossession => <C as BlsSignaturePop>::SIG_DST,
         };
         let (u, v, w) = <C as BlsSignCrypt>::seal(self.0, msg.as_ref(), dst);
-        SignCryptCiphertext { u, v, w, scheme }
+        SignCryptCiphertext {
+            u,
+            v,
+            w,
+            scheme,
+            header: Vec::new(),
+        }
+    }
+
+    /// Encrypt a message using signcryption with a specified RNG
+    pub fn sign_crypt_with_rng<B: AsRef<[u8]>>(
+        &self,
+        scheme: SignatureSchemes,
+        msg: B,
+        rng: impl RngCore + CryptoRng,
+    ) -> SignCryptCiphertext<C> {
+        let dst = sign_crypt_dst::<C>(scheme, &[]);
+        let (u, v, w) = <C as BlsSignCrypt>::seal_with_rng(self.0, msg.as_ref(), &dst, rng);
+        SignCryptCiphertext {
+            u,
+            v,
+            w,
+            scheme,
+            header: Vec::new(),
+        }
+    }
+
+    /// Encrypt a message using signcryption binding a cleartext `header`.
+    ///
+    /// The header travels unencrypted with the ciphertext but is authenticated
+    /// by `w`, so tampering with or removing it makes the ciphertext invalid.
+    /// An empty header produces the same ciphertext as [`PublicKey::sign_crypt`]
+    pub fn sign_crypt_with_header<B: AsRef<[u8]>, H: AsRef<[u8]>>(
+        &self,
+        scheme: SignatureSchemes,
+        msg: B,
+        header: H,
+    ) -> SignCryptCiphertext<C> {
+        self.sign_crypt_with_header_with_rng(scheme, msg, header, get_crypto_rng())
+    }
+
+    /// Encrypt a message using signcryption binding a cleartext `header`
+    /// with a specified RNG
+    pub fn sign_crypt_with_header_with_rng<B: AsRef<[u8]>, H: AsRef<[u8]>>(
+        &self,
+        scheme: SignatureSchemes,
+        msg: B,
+        header: H,
+        rng: impl RngCore + CryptoRng,
+    ) -> SignCryptCiphertext<C> {
+        let header = header.as_ref().to_vec();
+        let dst = sign_crypt_dst::<C>(scheme, &header);
+        let (u, v, w) = <C as BlsSignCrypt>::seal_with_rng(self.0, msg.as_ref(), &dst, rng);
+        SignCryptCiphertext {
+            u,
+            v,
+            w,
+            scheme,
+            header,
+        }
+    }
+
+    /// Encrypt a batch of messages to this public key using signcryption.
+    ///
+    /// Each ciphertext is the same as one from [`PublicKey::sign_crypt`].
+    /// The multiples of this key used to encrypt are precomputed once and
+    /// shared across the batch, replacing a scalar multiplication per message
+    /// with table additions.
+    /// Ephemeral keys are NOT reused: every message gets its own fresh `r`
+    pub fn seal_batch(
+        &self,
+        msgs: &[&[u8]],
+        scheme: SignatureSchemes,
+    ) -> Vec<SignCryptCiphertext<C>> {
+        self.seal_batch_with_rng(msgs, scheme, get_crypto_rng())
+    }
+
+    /// Encrypt a batch of messages to this public key using signcryption
+    /// with a specified RNG.
+    ///
+    /// A fresh ephemeral key is drawn from `rng` for every message
+    pub fn seal_batch_with_rng(
+        &self,
+        msgs: &[&[u8]],
+        scheme: SignatureSchemes,
+        rng: impl RngCore + CryptoRng,
+    ) -> Vec<SignCryptCiphertext<C>> {
+        let dst = sign_crypt_dst::<C>(scheme, &[]);
+        <C as BlsSignCrypt>::seal_batch_with_rng(self.0, msgs, &dst, rng)
+            .into_iter()
+            .map(|(u, v, w)| SignCryptCiphertext {
+                u,
+                v,
+                w,
+                scheme,
+                header: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Encrypt a message using signcryption padding the encrypted message to a
+    /// multiple of `block_size` bytes so the ciphertext length only reveals the
+    /// number of blocks.
+    ///
+    /// Padded ciphertexts are decrypted like any other and the padding is
+    /// authenticated along with the rest of the ciphertext
+    pub fn seal_padded<B: AsRef<[u8]>>(
+        &self,
+        scheme: SignatureSchemes,
+        msg: B,
+        block_size: usize,
+    ) -> BlsResult<SignCryptCiphertext<C>> {
+        self.seal_padded_with_rng(scheme, msg, block_size, get_crypto_rng())
+    }
+
+    /// Encrypt a message using signcryption with padding and a specified RNG
+    pub fn seal_padded_with_rng<B: AsRef<[u8]>>(
+        &self,
+        scheme: SignatureSchemes,
+        msg: B,
+        block_size: usize,
+        rng: impl RngCore + CryptoRng,
+    ) -> BlsResult<SignCryptCiphertext<C>> {
+        if block_size == 0 {
+            return Err(BlsError::InvalidInputs(
+                "block size cannot be zero".to_string(),
+            ));
+        }
+        let dst = sign_crypt_dst::<C>(scheme, &[]);
+        let (u, v, w) =
+            <C as BlsSignCrypt>::seal_padded_with_rng(self.0, msg.as_ref(), &dst, block_size, rng);
+        Ok(SignCryptCiphertext {
+            u,
+            v,
+            w,
+            scheme,
+            header: Vec::new(),
+        })
     }
 
     /// Encrypt a message using time lock encryption
@@ -106,6 +328,19 @@ impl<C: BlsSignatureImpl> PublicKey<C> {
         Ok(TimeCryptCiphertext { u, v, w, scheme })
     }
 
+    /// Lock a message to a future `target` of the beacon with this public key.
+    ///
+    /// The ciphertext can only be decrypted with the beacon's signature over
+    /// the target using `scheme`, see [`TimeCryptCiphertext::decrypt`]
+    pub fn time_lock<B: AsRef<[u8]>>(
+        &self,
+        msg: B,
+        target: TimeLockTarget,
+        scheme: SignatureSchemes,
+    ) -> BlsResult<TimeCryptCiphertext<C>> {
+        self.encrypt_time_lock(scheme, msg, target.to_bytes())
+    }
+
     /// Encrypt a message using ElGamal
     pub fn encrypt_key_el_gamal(&self, sk: &SecretKey<C>) -> BlsResult<ElGamalCiphertext<C>> {
         let (c1, c2) = <C as BlsElGamal>::seal_scalar(self.0, sk.0, None, None, get_crypto_rng())?;
@@ -124,12 +359,25 @@ impl<C: BlsSignatureImpl> PublicKey<C> {
         })
     }
 
+    /// Compute the augmented message used by the message augmentation scheme.
+    ///
+    /// The result is `public_key || msg` where the public key is in compressed form.
+    /// This is the exact input given to hash to curve when signing or verifying.
+    pub fn augment_message<B: AsRef<[u8]>>(&self, msg: B) -> Vec<u8> {
+        let msg = msg.as_ref();
+        let mut output = <C as BlsSignatureMessageAugmentation>::pk_bytes(self.0, msg.len());
+        output.extend_from_slice(msg);
+        output
+    }
+
     /// Create a public key from secret shares
     pub fn from_shares(shares: &[PublicKeyShare<C>]) -> BlsResult<Self> {
         let points = shares
             .iter()
             .map(|s| s.0)
             .collect::<Vec<<C as Pairing>::PublicKeyShare>>();
-        <C as BlsSignatureCore>::core_combine_public_key_shares(&points).map(Self)
+        <C as BlsSignatureCore>::core_combine_public_key_shares(&points)
+            .map(Self)
+            .context("public key share combination")
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::num::NonZeroUsize;
 use subtle::Choice;
 
 /// A public key share is point on the curve.
@@ -8,7 +9,11 @@ use subtle::Choice;
 /// to produce the completed key, or used for
 /// creating partial signatures which can be
 /// combined into a complete signature
-#[derive(Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+///
+/// Like [`SecretKeyShare`] it serializes as the identifier then the
+/// compressed point, and that layout won't change without a major release.
+#[derive(Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PublicKeyShare<C: BlsSignatureImpl>(pub <C as Pairing>::PublicKeyShare);
 
 impl<C: BlsSignatureImpl> Copy for PublicKeyShare<C> {}
@@ -33,20 +38,40 @@ impl<C: BlsSignatureImpl> Display for PublicKeyShare<C> {
     }
 }
 
+impl<C: BlsSignatureImpl> From<&SecretKeyShare<C>> for PublicKeyShare<C> {
+    fn from(sks: &SecretKeyShare<C>) -> Self {
+        let pk = <C as Pairing>::PublicKey::generator() * sks.0.value().0;
+        Self(<C as Pairing>::PublicKeyShare::with_identifier_and_value(
+            *sks.0.identifier(),
+            ValueGroup(pk),
+        ))
+    }
+}
+
 impl_from_derivatives_generic!(PublicKeyShare);
 
 impl<C: BlsSignatureImpl> From<&PublicKeyShare<C>> for Vec<u8> {
     fn from(pk: &PublicKeyShare<C>) -> Vec<u8> {
-        serde_bare::to_vec(&pk.0).unwrap()
+        codec::encode(pk)
     }
 }
 
 impl<C: BlsSignatureImpl> TryFrom<&[u8]> for PublicKeyShare<C> {
     type Error = BlsError;
     fn try_from(bytes: &[u8]) -> BlsResult<Self> {
-        serde_bare::from_slice(bytes)
-            .map(Self)
-            .map_err(|e| BlsError::InvalidInputs(e.to_string()))
+        codec::decode(bytes).map_err(|e| BlsError::InvalidInputs(e.to_string()))
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Encode for PublicKeyShare<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.point_share(&self.0);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for PublicKeyShare<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        r.point_share().map(Self)
     }
 }
 
@@ -70,6 +95,74 @@ impl<C: BlsSignatureImpl> PublicKeyShare<C> {
         }
     }
 
+    /// Check this share against a dealer's Feldman commitments in isolation.
+    ///
+    /// Only this share's identifier and the commitments to the polynomial
+    /// coefficients are needed so a participant can validate their share
+    /// before the rest of the committee is known
+    pub fn verify_single(&self, commitments: &[PublicKey<C>]) -> Choice {
+        let x = self.0.identifier().0;
+        let mut expected = <C as Pairing>::PublicKey::identity();
+        for c in commitments.iter().rev() {
+            expected = expected * x + c.0;
+        }
+        !x.is_zero()
+            & Choice::from(u8::from(!commitments.is_empty()))
+            & (expected - self.0.value().0).is_identity()
+    }
+
+    /// Evaluate the sharing polynomial in the exponent at `x` to compute the
+    /// public key share a participant with identifier `x` would have.
+    ///
+    /// At least threshold many `shares` are needed for the result to lie on
+    /// the dealer's polynomial. This lets a committee onboard a new member
+    /// using only the public shares.
+    pub fn interpolate_at(shares: &[PublicKeyShare<C>], x: NonZeroUsize) -> BlsResult<Self> {
+        if shares.is_empty() {
+            return Err(BlsError::InvalidInputs("no shares provided".to_string()));
+        }
+        let ids = shares
+            .iter()
+            .map(|s| s.0.identifier().0)
+            .collect::<Vec<_>>();
+        for (i, id) in ids.iter().enumerate() {
+            if id.is_zero().into() {
+                return Err(BlsError::InvalidInputs(format!(
+                    "share at {} has a zero identifier",
+                    i
+                )));
+            }
+            if ids[..i].contains(id) {
+                return Err(BlsError::InvalidInputs(format!(
+                    "duplicate share identifier at {}",
+                    i
+                )));
+            }
+        }
+
+        let x = <<C as Pairing>::PublicKey as Group>::Scalar::from(x.get() as u64);
+        let mut value = <C as Pairing>::PublicKey::identity();
+        for (i, (share, xi)) in shares.iter().zip(&ids).enumerate() {
+            let mut num = <<C as Pairing>::PublicKey as Group>::Scalar::ONE;
+            let mut den = <<C as Pairing>::PublicKey as Group>::Scalar::ONE;
+            for (j, xj) in ids.iter().enumerate() {
+                if i != j {
+                    num *= x - xj;
+                    den *= *xi - xj;
+                }
+            }
+            // identifiers are distinct so the denominator is never zero
+            let basis = num * den.invert().unwrap();
+            value += share.0.value().0 * basis;
+        }
+        Ok(Self(
+            <C as Pairing>::PublicKeyShare::with_identifier_and_value(
+                IdentifierPrimeField(x),
+                ValueGroup(value),
+            ),
+        ))
+    }
+
     /// Convert a share byte sequence from version 1 to a public key share
     /// that was output from converting to Vec<u8>
     pub fn from_v1_inner_bytes(raw_bytes: &[u8]) -> BlsResult<Self> {
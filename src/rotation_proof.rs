@@ -0,0 +1,229 @@
+use crate::impls::inner_types::*;
+use crate::*;
+use rand_core::{CryptoRng, RngCore};
+use vsss_rs::*;
+
+/// A proof that a resharing to a new committee kept the same group key
+///
+/// The proof wraps the [`DealerProof`] for the polynomial used for the new
+/// shares. The first commitment is the group public key so verifiers can
+/// confirm continuity with [`RotationProof::verify_rotation`] and each new
+/// member can check their share with [`RotationProof::verify_share`]
+/// without any share being revealed.
+#[derive(Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound = ""))]
+pub struct RotationProof<C: BlsSignatureImpl>(pub DealerProof<C>);
+
+impl<C: BlsSignatureImpl> fmt::Debug for RotationProof<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "RotationProof({:?})", self.0)
+    }
+}
+
+impl<C: BlsSignatureImpl> Clone for RotationProof<C> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl_from_derivatives_generic!(RotationProof);
+
+impl<C: BlsSignatureImpl> From<&RotationProof<C>> for Vec<u8> {
+    fn from(value: &RotationProof<C>) -> Self {
+        codec::to_vec(value)
+    }
+}
+
+impl<C: BlsSignatureImpl> TryFrom<&[u8]> for RotationProof<C> {
+    type Error = BlsError;
+
+    fn try_from(value: &[u8]) -> BlsResult<Self> {
+        let output = codec::from_slice(value)?;
+        Ok(output)
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Encode for RotationProof<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        self.0.encode(w);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for RotationProof<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        DealerProof::decode(r).map(Self)
+    }
+}
+
+impl<C: BlsSignatureImpl> RotationProof<C> {
+    /// The group public key committed to by this proof
+    pub fn group_public_key(&self) -> BlsResult<PublicKey<C>> {
+        self.0.group_public_key()
+    }
+
+    /// The number of shares required to combine the new shares
+    pub fn threshold(&self) -> usize {
+        self.0.threshold()
+    }
+
+    /// Verify the rotation kept the group public key the same.
+    ///
+    /// The commitments must start with `old_group_pub` and every one of
+    /// `new_shares` must lie on the committed polynomial. At least threshold
+    /// many shares with distinct identifiers are required since checking the
+    /// commitments alone proves nothing: anyone can put `old_group_pub` first.
+    /// Shares that pass combine to `old_group_pub`, so the new committee holds
+    /// the old group key.
+    pub fn verify_rotation(
+        &self,
+        old_group_pub: &PublicKey<C>,
+        new_shares: &[PublicKeyShare<C>],
+    ) -> BlsResult<()> {
+        self.0.verify(old_group_pub)?;
+        if new_shares.len() < self.threshold() {
+            return Err(BlsError::InsufficientShares {
+                threshold: self.threshold(),
+                received: new_shares.len(),
+            });
+        }
+        for (i, share) in new_shares.iter().enumerate() {
+            let id = share.0.identifier();
+            if new_shares[..i].iter().any(|s| s.0.identifier() == id) {
+                return Err(BlsError::InvalidInputs(format!(
+                    "duplicate share identifier at {}",
+                    i
+                )));
+            }
+            self.verify_share(share)?;
+        }
+        Ok(())
+    }
+
+    /// Verify a new public key share is consistent with the committed polynomial
+    pub fn verify_share(&self, share: &PublicKeyShare<C>) -> BlsResult<()> {
+        self.0.verify_share(share)
+    }
+}
+
+impl<C: BlsSignatureImpl> SecretKeyShare<C> {
+    /// Deal this share to a new committee of `config.limit()` members where
+    /// `config.threshold()` are required to combine.
+    ///
+    /// Every old holder taking part deals only their own share, so no party
+    /// ever sees another's share. Each new member checks and combines the
+    /// dealings sent to them with [`SecretKeyShare::combine_reshares`]. The
+    /// returned [`DealerProof`] commits to this dealing and its first
+    /// commitment is this share's public key share.
+    pub fn reshare(
+        &self,
+        config: ThresholdConfig,
+    ) -> BlsResult<(Vec<SecretKeyShare<C>>, DealerProof<C>)> {
+        self.reshare_with_rng(config, get_crypto_rng())
+    }
+
+    /// Deal this share to a new committee using a specified RNG
+    pub fn reshare_with_rng(
+        &self,
+        config: ThresholdConfig,
+        rng: impl RngCore + CryptoRng,
+    ) -> BlsResult<(Vec<SecretKeyShare<C>>, DealerProof<C>)> {
+        let value = self.0.value().0;
+        if value.is_zero().into() {
+            return Err(BlsError::InvalidInputs(
+                "share value cannot be zero".to_string(),
+            ));
+        }
+        let coefficients = random_poly(value, config.threshold(), rng);
+        let shares = (1..=config.limit())
+            .map(|j| {
+                let x = <<C as Pairing>::PublicKey as Group>::Scalar::from(j as u64);
+                SecretKeyShare(<C as Pairing>::SecretKeyShare::with_identifier_and_value(
+                    IdentifierPrimeField(x),
+                    IdentifierPrimeField(eval_poly(&coefficients, x)),
+                ))
+            })
+            .collect();
+        Ok((shares, DealerProof::commit(&coefficients)))
+    }
+
+    /// Combine the dealings this new member received from the old holders
+    /// into its share of the unchanged group secret.
+    ///
+    /// Each dealing is the old holder's public key share, the share dealt to
+    /// this member by [`SecretKeyShare::reshare`] and the holder's
+    /// [`DealerProof`]. Each proof must commit to the holder's public key
+    /// share and each dealt share must lie on it. At least
+    /// `old_config.threshold()` holders with distinct identifiers are required
+    /// or the new shares would be of a different key, so fewer fail with
+    /// [`BlsError::InsufficientShares`]. The returned [`RotationProof`] is the
+    /// same for every member that combines the same dealings.
+    pub fn combine_reshares(
+        old_config: ThresholdConfig,
+        dealings: &[(PublicKeyShare<C>, SecretKeyShare<C>, DealerProof<C>)],
+    ) -> BlsResult<(SecretKeyShare<C>, RotationProof<C>)> {
+        let dealers = dealings.iter().map(|(d, _, _)| d.0).collect::<Vec<_>>();
+        old_config.check_shares(&dealers)?;
+        let (_, first_share, first_proof) = &dealings[0];
+        let identifier = *first_share.0.identifier();
+        let threshold = first_proof.threshold();
+
+        for (i, (dealer, share, proof)) in dealings.iter().enumerate() {
+            if dealers[..i]
+                .iter()
+                .any(|d| d.identifier() == dealer.0.identifier())
+            {
+                return Err(BlsError::InvalidInputs(format!(
+                    "duplicate dealer identifier at {}",
+                    i
+                )));
+            }
+            if proof.threshold() != threshold {
+                return Err(BlsError::InvalidInputs(format!(
+                    "dealing at {} has a different threshold",
+                    i
+                )));
+            }
+            if *share.0.identifier() != identifier {
+                return Err(BlsError::InvalidInputs(format!(
+                    "dealing at {} is for a different share identifier",
+                    i
+                )));
+            }
+            proof.verify(&PublicKey(dealer.0.value().0))?;
+            proof.verify_feldman(share)?;
+        }
+
+        // The dealings are combined at the old identifiers
+        let value = dealings
+            .iter()
+            .map(|(dealer, share, _)| {
+                <C as Pairing>::SecretKeyShare::with_identifier_and_value(
+                    *dealer.0.identifier(),
+                    *share.0.value(),
+                )
+            })
+            .collect::<Vec<_>>()
+            .combine()?;
+        let mut commitments = Vec::with_capacity(threshold);
+        for k in 0..threshold {
+            let points = dealings
+                .iter()
+                .map(|(dealer, _, proof)| {
+                    <C as Pairing>::PublicKeyShare::with_identifier_and_value(
+                        *dealer.0.identifier(),
+                        ValueGroup(proof.commitments[k].0),
+                    )
+                })
+                .collect::<Vec<_>>();
+            commitments.push(PublicKey(
+                <C as BlsSignatureCore>::core_combine_public_key_shares(&points)?,
+            ));
+        }
+
+        let share = SecretKeyShare(<C as Pairing>::SecretKeyShare::with_identifier_and_value(
+            identifier, value,
+        ));
+        Ok((share, RotationProof(DealerProof { commitments })))
+    }
+}
@@ -1,9 +1,11 @@
 use crate::helpers::{get_crypto_rng, KEYGEN_SALT};
 use crate::impls::inner_types::*;
 use crate::*;
+#[cfg(feature = "serde")]
 use core::fmt::{self, Formatter};
 use rand::Rng;
 use rand_core::{CryptoRng, RngCore};
+#[cfg(feature = "serde")]
 use serde::de::{SeqAccess, Visitor};
 use subtle::CtOption;
 use vsss_rs::*;
@@ -28,6 +30,7 @@ pub enum SecretKeyEnum {
     G2(SecretKey<Bls12381G2Impl>),
 }
 
+#[cfg(feature = "serde")]
 impl Serialize for SecretKeyEnum {
     fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
         match self {
@@ -37,6 +40,7 @@ impl Serialize for SecretKeyEnum {
     }
 }
 
+#[cfg(feature = "serde")]
 impl<'de> Deserialize<'de> for SecretKeyEnum {
     fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
         struct SecretKeyEnumVisitor;
@@ -227,11 +231,17 @@ impl SecretKeyEnum {
 /// The secret key is field element 0 < `x` < `r`
 /// where `r` is the curve order. See Section 4.3 in
 /// <https://eprint.iacr.org/2016/663.pdf>
-#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SecretKey<C: BlsSignatureImpl>(
     /// The secret key raw value
-    #[serde(serialize_with = "traits::scalar::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::scalar::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::scalar::serialize::<C, _>",
+            deserialize_with = "traits::scalar::deserialize_non_zero::<C, _>"
+        )
+    )]
     pub <<C as Pairing>::PublicKey as Group>::Scalar,
 );
 
@@ -261,8 +271,7 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for SecretKey<C> {
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
         let bytes = <[u8; 32]>::try_from(value)
             .map_err(|_| BlsError::InvalidInputs("Invalid secret key bytes".to_string()))?;
-        Option::from(Self::from_be_bytes(&bytes))
-            .ok_or_else(|| BlsError::InvalidInputs("Invalid secret key bytes".to_string()))
+        Option::from(Self::from_be_bytes(&bytes)).ok_or(BlsError::InvalidSecretKey)
     }
 }
 
@@ -273,6 +282,9 @@ impl<C: BlsSignatureImpl> SecretKey<C> {
     }
 
     /// Compute a secret key from a hash
+    ///
+    /// The hash is not checked for zero, use [`SecretKey::try_from_hash`]
+    /// when `data` isn't already known to give a valid key
     pub fn from_hash<B: AsRef<[u8]>>(data: B) -> Self {
         Self(<C as HashToScalar>::hash_to_scalar(
             data.as_ref(),
@@ -280,12 +292,44 @@ impl<C: BlsSignatureImpl> SecretKey<C> {
         ))
     }
 
+    /// Compute a secret key from a hash failing with
+    /// [`BlsError::InvalidSecretKey`] if the hash is zero
+    pub fn try_from_hash<B: AsRef<[u8]>>(data: B) -> BlsResult<Self> {
+        Self::from_hash(data).non_zero()
+    }
+
+    /// Derive a secret key from a low-entropy passphrase such as a mnemonic.
+    ///
+    /// The passphrase is stretched with PBKDF2-HMAC-SHA512 using the cost in
+    /// `params` and the result is hashed to a key like [`SecretKey::from_hash`].
+    /// The same passphrase, salt and parameters always give the same key.
+    pub fn from_passphrase<B: AsRef<[u8]>, S: AsRef<[u8]>>(
+        passphrase: B,
+        salt: S,
+        params: KdfParams,
+    ) -> BlsResult<Self> {
+        let seed = params.derive_seed(passphrase.as_ref(), salt.as_ref());
+        Self::try_from_hash(seed.as_ref())
+    }
+
     /// Compute a secret key from a CS-PRNG
+    ///
+    /// A zero key is never returned, the RNG is sampled again instead
     pub fn random(mut rng: impl RngCore + CryptoRng) -> Self {
-        Self(<C as HashToScalar>::hash_to_scalar(
-            rng.gen::<[u8; SECRET_KEY_BYTES]>(),
-            KEYGEN_SALT,
-        ))
+        loop {
+            let sk = Self(<C as HashToScalar>::hash_to_scalar(
+                rng.gen::<[u8; SECRET_KEY_BYTES]>(),
+                KEYGEN_SALT,
+            ));
+            if !bool::from(sk.is_zero()) {
+                return sk;
+            }
+        }
+    }
+
+    /// Check if this key is zero which is never a valid secret key
+    pub fn is_zero(&self) -> Choice {
+        self.0.is_zero()
     }
 
     /// Get the big-endian byte representation of this key
@@ -299,13 +343,57 @@ impl<C: BlsSignatureImpl> SecretKey<C> {
     }
 
     /// Convert a big-endian representation of the secret key.
+    ///
+    /// Zero is rejected
     pub fn from_be_bytes(bytes: &[u8; SECRET_KEY_BYTES]) -> CtOption<Self> {
-        scalar_from_be_bytes::<C, SECRET_KEY_BYTES>(bytes).map(Self)
+        scalar_from_be_bytes::<C, SECRET_KEY_BYTES>(bytes)
+            .and_then(|s| CtOption::new(Self(s), !s.is_zero()))
     }
 
     /// Convert a little-endian representation of the secret key.
+    ///
+    /// Zero is rejected
     pub fn from_le_bytes(bytes: &[u8; SECRET_KEY_BYTES]) -> CtOption<Self> {
-        scalar_from_le_bytes::<C, SECRET_KEY_BYTES>(bytes).map(Self)
+        scalar_from_le_bytes::<C, SECRET_KEY_BYTES>(bytes)
+            .and_then(|s| CtOption::new(Self(s), !s.is_zero()))
+    }
+
+    /// Export this key as a big-endian hex string.
+    ///
+    /// # Danger
+    ///
+    /// This exposes the raw secret key. Anyone who sees the output can sign
+    /// as this key, so never log it, print it or send it anywhere that is not
+    /// meant to hold the secret. The returned string is zeroized on drop but
+    /// any copies made from it are not.
+    #[cfg(feature = "secret-export")]
+    pub fn to_hex(&self) -> zeroize::Zeroizing<String> {
+        let bytes = zeroize::Zeroizing::new(self.to_be_bytes());
+        zeroize::Zeroizing::new(hex::encode(bytes.as_ref()))
+    }
+
+    /// Import a key from a big-endian hex string created by [`SecretKey::to_hex`].
+    ///
+    /// # Danger
+    ///
+    /// The input is the raw secret key and should be handled with the same care
+    /// as the output of [`SecretKey::to_hex`]. Non-canonical and zero scalars are
+    /// rejected.
+    #[cfg(feature = "secret-export")]
+    pub fn from_hex(hex_str: &str) -> BlsResult<Self> {
+        let mut bytes = zeroize::Zeroizing::new([0u8; SECRET_KEY_BYTES]);
+        hex::decode_to_slice(hex_str, bytes.as_mut())
+            .map_err(|_| BlsError::InvalidInputs("Invalid secret key hex".to_string()))?;
+        let sk =
+            Option::<Self>::from(Self::from_be_bytes(&bytes)).ok_or(BlsError::InvalidSecretKey)?;
+        // Some backends reduce out of range values instead of rejecting them
+        let canonical = zeroize::Zeroizing::new(sk.to_be_bytes());
+        if canonical[..] != bytes[..] {
+            return Err(BlsError::InvalidInputs(
+                "secret key is not canonical".to_string(),
+            ));
+        }
+        Ok(sk)
     }
 
     /// Secret share this key by creating `limit` shares where `threshold` are required
@@ -334,10 +422,23 @@ impl<C: BlsSignatureImpl> SecretKey<C> {
     /// Reconstruct a secret from shares created from `split`
     pub fn combine(shares: &[SecretKeyShare<C>]) -> BlsResult<Self> {
         let ss = shares.iter().map(|s| s.0.clone()).collect::<Vec<_>>();
-        let secret = ss.combine()?;
+        let secret = ss
+            .combine()
+            .map_err(|e| BlsError::from(e).context("secret key share combination"))?;
+        if secret.0.is_zero().into() {
+            return Err(BlsError::InvalidSecretKey.context("secret key share combination"));
+        }
         Ok(Self(secret.0))
     }
 
+    /// Reject a zero key with [`BlsError::InvalidSecretKey`]
+    fn non_zero(self) -> BlsResult<Self> {
+        if self.is_zero().into() {
+            return Err(BlsError::InvalidSecretKey);
+        }
+        Ok(self)
+    }
+
     /// Compute the public key
     pub fn public_key(&self) -> PublicKey<C> {
         PublicKey(<C as BlsSignatureCore>::public_key(&self.0))
@@ -352,20 +453,82 @@ impl<C: BlsSignatureImpl> SecretKey<C> {
 
     /// Sign a message with this secret key using the specified scheme
     pub fn sign(&self, scheme: SignatureSchemes, msg: &[u8]) -> BlsResult<Signature<C>> {
-        match scheme {
-            SignatureSchemes::Basic => {
-                let inner = <C as BlsSignatureBasic>::sign(&self.0, msg)?;
-                Ok(Signature::Basic(inner))
-            }
-            SignatureSchemes::MessageAugmentation => {
-                let inner = <C as BlsSignatureMessageAugmentation>::sign(&self.0, msg)?;
-                Ok(Signature::MessageAugmentation(inner))
-            }
-            SignatureSchemes::ProofOfPossession => {
-                let inner = <C as BlsSignaturePop>::sign(&self.0, msg)?;
-                Ok(Signature::ProofOfPossession(inner))
+        traced!("bls.sign", { scheme = %scheme, msg_len = msg.len() }, {
+            match scheme {
+                SignatureSchemes::Basic => {
+                    let inner = <C as BlsSignatureBasic>::sign(&self.0, msg)?;
+                    Ok(Signature::Basic(inner))
+                }
+                SignatureSchemes::MessageAugmentation => {
+                    let inner = <C as BlsSignatureMessageAugmentation>::sign(&self.0, msg)?;
+                    Ok(Signature::MessageAugmentation(inner))
+                }
+                SignatureSchemes::ProofOfPossession => {
+                    let inner = <C as BlsSignaturePop>::sign(&self.0, msg)?;
+                    Ok(Signature::ProofOfPossession(inner))
+                }
             }
+        })
+    }
+
+    /// Sign a message and also return the hash to curve point of the message.
+    ///
+    /// The point is the one the signature is computed over for `scheme`,
+    /// including the public key prefix for message augmentation, and can be
+    /// given to [`Signature::verify_point`] to avoid hashing the message again.
+    pub fn sign_returning_hash(
+        &self,
+        scheme: SignatureSchemes,
+        msg: &[u8],
+    ) -> BlsResult<(Signature<C>, <C as Pairing>::Signature)> {
+        if self.0.is_zero().into() {
+            return Err(BlsError::SigningError("signing key is zero".to_string()));
         }
+        let hash = hash_for_scheme::<C>(scheme, self.public_key().0, msg);
+        Ok((Signature::from_raw(scheme, hash * self.0), hash))
+    }
+
+    /// Sign a message provided as multiple segments, e.g. a header and a body.
+    ///
+    /// The result is identical to signing the concatenation of `segments`,
+    /// and an empty list is the same as signing an empty message. The
+    /// hash to curve implementations take a single contiguous input so the
+    /// segments are joined into one buffer before hashing.
+    pub fn sign_segments(
+        &self,
+        scheme: SignatureSchemes,
+        segments: &[&[u8]],
+    ) -> BlsResult<Signature<C>> {
+        self.sign(scheme, &segments.concat())
+    }
+
+    /// Sign EIP-712 style structured data.
+    ///
+    /// The signed message is `0x19 0x01 || domain_separator || struct_hash`
+    /// which is then hashed to curve as usual for `scheme`, so the signature
+    /// is bound to both the domain and the structured payload
+    pub fn sign_structured(
+        &self,
+        scheme: SignatureSchemes,
+        domain_separator: &[u8; 32],
+        struct_hash: &[u8; 32],
+    ) -> BlsResult<Signature<C>> {
+        self.sign(scheme, &structured_message(domain_separator, struct_hash))
+    }
+
+    /// Sign a precomputed 32 byte digest, e.g. the SHA-256 of a document.
+    ///
+    /// The signed message is `b"BLS_DIGEST32_" || digest` which is then hashed
+    /// to curve as usual for `scheme`. The prefix keeps these signatures
+    /// distinct from [`SecretKey::sign`] over the digest bytes, so
+    /// `sign_digest(scheme, d)` equals `sign(scheme, &[b"BLS_DIGEST32_", d].concat())`
+    /// and never `sign(scheme, d)`
+    pub fn sign_digest(
+        &self,
+        scheme: SignatureSchemes,
+        digest: &[u8; 32],
+    ) -> BlsResult<Signature<C>> {
+        self.sign(scheme, &digest_message(digest))
     }
 
     /// Create a Signcrypt decryption key where the secret key is hidden
@@ -377,3 +540,22 @@ impl<C: BlsSignatureImpl> SecretKey<C> {
         SignCryptDecryptionKey(ciphertext.u * self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::*;
+
+    #[rstest]
+    #[case::g1(Bls12381G1Impl)]
+    #[case::g2(Bls12381G2Impl)]
+    fn try_from_hash_rejects_zero<C: BlsSignatureImpl + PartialEq + Eq + fmt::Debug>(
+        #[case] _c: C,
+    ) {
+        let zero = SecretKey::<C>(<<C as Pairing>::PublicKey as Group>::Scalar::ZERO);
+        assert!(matches!(zero.non_zero(), Err(BlsError::InvalidSecretKey)));
+
+        let sk = SecretKey::<C>::try_from_hash(b"try_from_hash").unwrap();
+        assert_eq!(sk, SecretKey::<C>::from_hash(b"try_from_hash"));
+    }
+}
@@ -1,5 +1,7 @@
 use crate::*;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use subtle::Choice;
 
 /// A secret key share is field element 0 < `x` < `r`
 /// where `r` is the curve order.
@@ -9,10 +11,19 @@ use serde::{Deserialize, Serialize};
 /// to produce the completed key, or used for
 /// creating partial signatures which can be
 /// combined into a complete signature
-#[derive(Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+///
+/// The serde layout, the identifier followed by the value, is stable
+/// across releases.
+#[derive(Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SecretKeyShare<C: BlsSignatureImpl>(
-    #[serde(serialize_with = "traits::secret_key_share::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::secret_key_share::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::secret_key_share::serialize::<C, _>",
+            deserialize_with = "traits::secret_key_share::deserialize::<C, _>"
+        )
+    )]
     pub <C as Pairing>::SecretKeyShare,
 );
 
@@ -26,7 +37,7 @@ impl_from_derivatives_generic!(SecretKeyShare);
 
 impl<C: BlsSignatureImpl> From<&SecretKeyShare<C>> for Vec<u8> {
     fn from(sk: &SecretKeyShare<C>) -> Self {
-        serde_bare::to_vec(sk).unwrap()
+        codec::to_vec(sk)
     }
 }
 
@@ -34,7 +45,19 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for SecretKeyShare<C> {
     type Error = BlsError;
 
     fn try_from(bytes: &[u8]) -> BlsResult<Self> {
-        serde_bare::from_slice(bytes).map_err(|e| BlsError::InvalidInputs(e.to_string()))
+        codec::from_slice(bytes).map_err(|e| BlsError::InvalidInputs(e.to_string()))
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Encode for SecretKeyShare<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.scalar_share(&self.0);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for SecretKeyShare<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        r.scalar_share().map(Self)
     }
 }
 
@@ -52,16 +75,53 @@ impl<C: BlsSignatureImpl> SecretKeyShare<C> {
         scheme: SignatureSchemes,
         msg: B,
     ) -> BlsResult<SignatureShare<C>> {
-        match scheme {
-            SignatureSchemes::Basic => Ok(SignatureShare::Basic(
-                <C as BlsSignatureBasic>::partial_sign(&self.0, msg)?,
-            )),
-            SignatureSchemes::MessageAugmentation => Err(BlsError::SigningError(
-                "Message Augmentation not supported".to_string(),
-            )),
-            SignatureSchemes::ProofOfPossession => Ok(SignatureShare::ProofOfPossession(
-                <C as BlsSignaturePop>::partial_sign(&self.0, msg)?,
-            )),
+        traced!("bls.partial_sign", { scheme = %scheme, msg_len = msg.as_ref().len() }, {
+            match scheme {
+                SignatureSchemes::Basic => Ok(SignatureShare::Basic(
+                    <C as BlsSignatureBasic>::partial_sign(&self.0, msg)?,
+                )),
+                SignatureSchemes::MessageAugmentation => Err(BlsError::SigningError(
+                    "Message Augmentation not supported".to_string(),
+                )),
+                SignatureSchemes::ProofOfPossession => Ok(SignatureShare::ProofOfPossession(
+                    <C as BlsSignaturePop>::partial_sign(&self.0, msg)?,
+                )),
+            }
+        })
+    }
+
+    /// Check this share against the published group public key and the
+    /// public key shares of the other participants without revealing the secret.
+    ///
+    /// The public share derived from this secret share is interpolated at zero
+    /// together with `other_pub_shares` and must land on `group_pub`. Enough
+    /// shares to meet the threshold must be provided. Any entry in
+    /// `other_pub_shares` with this share's identifier must match the derived
+    /// public share.
+    pub fn is_consistent_with(
+        &self,
+        group_pub: &PublicKey<C>,
+        other_pub_shares: &[PublicKeyShare<C>],
+    ) -> Choice {
+        let own = match self.public_key() {
+            Ok(own) => own,
+            Err(_) => return Choice::from(0u8),
+        };
+        let mut matches_published = Choice::from(1u8);
+        let mut shares = Vec::with_capacity(other_pub_shares.len() + 1);
+        shares.push(own);
+        for share in other_pub_shares {
+            if share.0.identifier() == own.0.identifier() {
+                matches_published &= (share.0.value().0 - own.0.value().0).is_identity();
+            } else {
+                shares.push(*share);
+            }
+        }
+        match PublicKey::from_shares(&shares) {
+            Ok(pk) => {
+                matches_published & !group_pub.0.is_identity() & (pk.0 - group_pub.0).is_identity()
+            }
+            Err(_) => Choice::from(0u8),
         }
     }
 
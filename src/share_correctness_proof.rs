@@ -0,0 +1,186 @@
+use crate::impls::inner_types::*;
+use crate::*;
+use rand_core::{CryptoRng, RngCore};
+
+/// A proof that a signature share was computed with the secret key share
+/// behind a committed public key share.
+///
+/// This is a Chaum-Pedersen proof that the public key share and the
+/// signature share have the same discrete log with respect to the generator
+/// and the hashed message. Checking it needs no pairing and a valid proof
+/// binds the signer to the share they produced.
+#[derive(Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ShareCorrectnessProof<C: BlsSignatureImpl> {
+    /// The fiat-shamir heuristic challenge
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::scalar::serialize::<C, _>",
+            deserialize_with = "traits::scalar::deserialize::<C, _>"
+        )
+    )]
+    pub challenge: <<C as Pairing>::PublicKey as Group>::Scalar,
+    /// The response to the challenge
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::scalar::serialize::<C, _>",
+            deserialize_with = "traits::scalar::deserialize::<C, _>"
+        )
+    )]
+    pub response: <<C as Pairing>::PublicKey as Group>::Scalar,
+}
+
+impl<C: BlsSignatureImpl> Display for ShareCorrectnessProof<C> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{{challenge: {:?}, response: {:?}}}",
+            self.challenge, self.response
+        )
+    }
+}
+
+impl<C: BlsSignatureImpl> fmt::Debug for ShareCorrectnessProof<C> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "ShareCorrectnessProof{{challenge: {:?}, response: {:?}}}",
+            self.challenge, self.response
+        )
+    }
+}
+
+impl<C: BlsSignatureImpl> Copy for ShareCorrectnessProof<C> {}
+
+impl<C: BlsSignatureImpl> Clone for ShareCorrectnessProof<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: BlsSignatureImpl> From<&ShareCorrectnessProof<C>> for Vec<u8> {
+    fn from(value: &ShareCorrectnessProof<C>) -> Self {
+        codec::to_vec(value)
+    }
+}
+
+impl<C: BlsSignatureImpl> TryFrom<&[u8]> for ShareCorrectnessProof<C> {
+    type Error = BlsError;
+
+    fn try_from(value: &[u8]) -> BlsResult<Self> {
+        let proof = codec::from_slice(value)?;
+        Ok(proof)
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Encode for ShareCorrectnessProof<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.scalar(&self.challenge);
+        w.scalar(&self.response);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for ShareCorrectnessProof<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        Ok(Self {
+            challenge: r.scalar()?,
+            response: r.scalar()?,
+        })
+    }
+}
+
+impl_from_derivatives_generic!(ShareCorrectnessProof);
+
+impl<C: BlsSignatureImpl> ShareCorrectnessProof<C> {
+    /// Create a proof that `share` is `msg` signed by `sks`
+    pub(crate) fn generate(
+        share: &SignatureShare<C>,
+        sks: &SecretKeyShare<C>,
+        msg: &[u8],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> BlsResult<Self> {
+        let sig = share.as_raw_value();
+        if sig.identifier() != sks.0.identifier() {
+            return Err(BlsError::InvalidInputs(
+                "signature share and secret key share identifiers differ".to_string(),
+            ));
+        }
+        let x = sks.0.value().0;
+        let pk = <C as Pairing>::PublicKey::generator() * x;
+        let hash = hash_for_scheme::<C>(share.scheme(), pk, msg);
+        if hash * x != sig.value().0 {
+            return Err(BlsError::InvalidInputs(
+                "signature share was not created by the secret key share".to_string(),
+            ));
+        }
+
+        let k = <<C as Pairing>::PublicKey as Group>::Scalar::random(&mut rng);
+        let t1 = <C as Pairing>::PublicKey::generator() * k;
+        let t2 = hash * k;
+        let challenge = Self::challenge(share, pk, hash, t1, t2);
+        Ok(Self {
+            challenge,
+            response: k + challenge * x,
+        })
+    }
+
+    /// Check this proof for `share` over `msg` against `pks`
+    pub(crate) fn verify(
+        &self,
+        share: &SignatureShare<C>,
+        pks: &PublicKeyShare<C>,
+        msg: &[u8],
+    ) -> BlsResult<()> {
+        let sig = share.as_raw_value();
+        if sig.identifier() != pks.0.identifier() {
+            return Err(BlsError::InvalidInputs(
+                "signature share and public key share identifiers differ".to_string(),
+            ));
+        }
+        let pk = pks.0.value().0;
+        let sig = sig.value().0;
+        if pk.is_identity().into() {
+            return Err(BlsError::InvalidPublicKey);
+        }
+        if sig.is_identity().into() {
+            return Err(BlsError::InvalidSignature);
+        }
+        if (self.challenge.is_zero() | self.response.is_zero()).into() {
+            return Err(BlsError::InvalidProof);
+        }
+
+        let hash = hash_for_scheme::<C>(share.scheme(), pk, msg);
+        let neg_challenge = -self.challenge;
+        // t1 = g^(k + cx) * pk^-c
+        let t1 = <C as Pairing>::PublicKey::generator() * self.response + pk * neg_challenge;
+        // t2 = H(m)^(k + cx) * sig^-c
+        let t2 = hash * self.response + sig * neg_challenge;
+        if Self::challenge(share, pk, hash, t1, t2) != self.challenge {
+            return Err(BlsError::InvalidProof);
+        }
+        Ok(())
+    }
+
+    fn challenge(
+        share: &SignatureShare<C>,
+        pk: <C as Pairing>::PublicKey,
+        hash: <C as Pairing>::Signature,
+        t1: <C as Pairing>::PublicKey,
+        t2: <C as Pairing>::Signature,
+    ) -> <<C as Pairing>::PublicKey as Group>::Scalar {
+        let sig = share.as_raw_value();
+        let mut transcript = merlin::Transcript::new(b"ShareCorrectnessProof");
+        transcript.append_message(b"scheme", &[share.scheme() as u8]);
+        transcript.append_message(b"identifier", sig.identifier().0.to_repr().as_ref());
+        transcript.append_message(b"pk share", pk.to_bytes().as_ref());
+        transcript.append_message(b"signature share", sig.value().0.to_bytes().as_ref());
+        transcript.append_message(b"hash", hash.to_bytes().as_ref());
+        transcript.append_message(b"t1", t1.to_bytes().as_ref());
+        transcript.append_message(b"t2", t2.to_bytes().as_ref());
+        let mut challenge = [0u8; 64];
+        transcript.challenge_bytes(b"challenge", &mut challenge);
+        <C as BlsElGamal>::scalar_from_bytes_wide(&challenge)
+    }
+}
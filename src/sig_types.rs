@@ -1,7 +1,7 @@
-use crate::BlsError;
+use crate::{codec, BlsError};
 
 /// The BLS signature algorithm schemes
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[repr(u8)]
 pub enum SignatureSchemes {
     /// The basic signature algorithm scheme
@@ -9,12 +9,22 @@ pub enum SignatureSchemes {
     /// The message augmentation signature algorithm scheme
     MessageAugmentation = 1,
     /// The proof of possession signature algorithm scheme
+    #[default]
     ProofOfPossession = 2,
 }
 
-impl Default for SignatureSchemes {
-    fn default() -> Self {
-        Self::ProofOfPossession
+impl SignatureSchemes {
+    /// Whether aggregating signatures under this scheme is only secure
+    /// when every signed message is distinct.
+    ///
+    /// The basic scheme relies on distinct messages to prevent rogue key
+    /// attacks while message augmentation binds each message to its signer
+    /// and proof of possession relies on the signers proving their keys.
+    pub fn requires_distinct_messages(&self) -> bool {
+        match self {
+            Self::Basic => true,
+            Self::MessageAugmentation | Self::ProofOfPossession => false,
+        }
     }
 }
 
@@ -60,6 +70,7 @@ impl core::str::FromStr for SignatureSchemes {
     }
 }
 
+#[cfg(feature = "serde")]
 impl serde::Serialize for SignatureSchemes {
     fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
     where
@@ -73,6 +84,7 @@ impl serde::Serialize for SignatureSchemes {
     }
 }
 
+#[cfg(feature = "serde")]
 impl<'de> serde::Deserialize<'de> for SignatureSchemes {
     fn deserialize<D>(d: D) -> Result<Self, D::Error>
     where
@@ -87,3 +99,15 @@ impl<'de> serde::Deserialize<'de> for SignatureSchemes {
         }
     }
 }
+
+impl codec::Encode for SignatureSchemes {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.u8(*self as u8);
+    }
+}
+
+impl codec::Decode for SignatureSchemes {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        r.u8().map(Self::from)
+    }
+}
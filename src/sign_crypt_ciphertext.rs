@@ -1,36 +1,302 @@
 use crate::*;
+#[cfg(feature = "serde")]
+use core::marker::PhantomData;
+#[cfg(feature = "serde")]
+use serde::de::{Error as _, SeqAccess, Visitor};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 use subtle::CtOption;
 
+const ROUTING_DST: &[u8] = b"SIGNCRYPT_BLS12381_ROUTING_SHA-256_";
+
+/// The envelope version written by this implementation
+const SIGN_CRYPT_VERSION: u8 = 1;
+
+/// The tag of the extension carrying the authenticated header
+const HEADER_EXTENSION: u8 = 1;
+
+/// True if `first` starts a ciphertext serialized before the envelope.
+///
+/// Those start with `u` and a compressed point's first byte has the
+/// compression flag set, which no version byte has
+fn is_legacy(first: u8) -> bool {
+    first & 0x80 != 0
+}
+
+/// The domain separation tag for a signcrypt ciphertext's `w`.
+///
+/// Without a header this is the scheme's signature tag. With one the SHA-256
+/// of the header is appended so `w` authenticates it, and the header can't be
+/// changed or removed without invalidating the ciphertext
+pub(crate) fn sign_crypt_dst<C: BlsSignatureImpl>(
+    scheme: SignatureSchemes,
+    header: &[u8],
+) -> Cow<'static, [u8]> {
+    let dst = match scheme {
+        SignatureSchemes::Basic => <C as BlsSignatureBasic>::DST,
+        SignatureSchemes::MessageAugmentation => <C as BlsSignatureMessageAugmentation>::DST,
+        SignatureSchemes::ProofOfPossession => <C as BlsSignaturePop>::SIG_DST,
+    };
+    if header.is_empty() {
+        return Cow::Borrowed(dst);
+    }
+    let mut dst = dst.to_vec();
+    dst.extend_from_slice(b"HDR_");
+    dst.extend_from_slice(&Sha256::digest(header));
+    Cow::Owned(dst)
+}
+
 /// The ciphertext output from sign crypt encryption
-#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+///
+/// The serialized form is a versioned envelope: a version byte, the
+/// ciphertext components and a length-prefixed extension area. Extensions
+/// added within the same version are skipped when decoding, and a ciphertext
+/// with an unknown version is rejected with [`BlsError::UnsupportedVersion`].
+/// A non-empty `header` is carried in the extension area. Ciphertexts
+/// serialized before the envelope, without a version or extensions, still
+/// decode.
+///
+/// The [`Default`] value is an invalid placeholder, see its documentation.
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub struct SignCryptCiphertext<C: BlsSignatureImpl> {
     /// The `u` component
-    #[serde(serialize_with = "traits::public_key::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::public_key::deserialize::<C, _>")]
     pub u: <C as Pairing>::PublicKey,
     /// The `v` component
     pub v: Vec<u8>,
     /// The `w` component
-    #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
     pub w: <C as Pairing>::Signature,
     /// The signature scheme used to generate this ciphertext
     pub scheme: SignatureSchemes,
+    /// Cleartext data authenticated by the sender along with the ciphertext,
+    /// empty if there is none. Changing it makes the ciphertext invalid
+    pub header: Vec<u8>,
+}
+
+/// An invalid placeholder ciphertext.
+///
+/// `u` and `w` are the identity and `v` is empty, so it never passes
+/// [`SignCryptCiphertext::is_valid`] and can't be decrypted. It only exists
+/// to be overwritten, e.g. by deserialization, and must never be treated as
+/// a real ciphertext.
+impl<C: BlsSignatureImpl> Default for SignCryptCiphertext<C> {
+    fn default() -> Self {
+        Self {
+            u: <C as Pairing>::PublicKey::identity(),
+            v: Vec::new(),
+            w: <C as Pairing>::Signature::identity(),
+            scheme: SignatureSchemes::default(),
+            header: Vec::new(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct SignCryptEnvelope<'a, C: BlsSignatureImpl> {
+    version: u8,
+    #[serde(serialize_with = "traits::public_key::serialize::<C, _>")]
+    u: <C as Pairing>::PublicKey,
+    v: Cow<'a, [u8]>,
+    #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
+    w: <C as Pairing>::Signature,
+    scheme: SignatureSchemes,
+    extensions: Cow<'a, [u8]>,
+}
+
+/// The fields of a ciphertext in a self describing format
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct SignCryptFields<C: BlsSignatureImpl> {
+    /// Missing for ciphertexts serialized before the envelope
+    #[serde(default)]
+    version: Option<u8>,
+    #[serde(deserialize_with = "traits::public_key::deserialize::<C, _>")]
+    u: <C as Pairing>::PublicKey,
+    v: Vec<u8>,
+    #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+    w: <C as Pairing>::Signature,
+    scheme: SignatureSchemes,
+    #[serde(default)]
+    extensions: Vec<u8>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct PublicKeyField<C: BlsSignatureImpl>(
+    #[serde(deserialize_with = "traits::public_key::deserialize::<C, _>")]
+    <C as Pairing>::PublicKey,
+);
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(bound = "")]
+struct SignatureField<C: BlsSignatureImpl>(
+    #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")] <C as Pairing>::Signature,
+);
+
+/// Reads a ciphertext from a binary format one field at a time so the
+/// first byte can tell an envelope from the layout before it
+#[cfg(feature = "serde")]
+struct SignCryptVisitor<C: BlsSignatureImpl>(PhantomData<C>);
+
+#[cfg(feature = "serde")]
+impl<'de, C: BlsSignatureImpl> Visitor<'de> for SignCryptVisitor<C> {
+    type Value = SignCryptCiphertext<C>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "a signcrypt ciphertext")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        fn next<'de, T: Deserialize<'de>, A: SeqAccess<'de>>(seq: &mut A) -> Result<T, A::Error> {
+            seq.next_element()?
+                .ok_or_else(|| A::Error::custom("unexpected end of signcrypt ciphertext"))
+        }
+
+        let first = next::<u8, _>(&mut seq)?;
+        let legacy = is_legacy(first);
+        let u = if legacy {
+            // The first byte is the start of `u`
+            let mut repr = <<C as Pairing>::PublicKey as GroupEncoding>::Repr::default();
+            let bytes = repr.as_mut();
+            bytes[0] = first;
+            for b in bytes[1..].iter_mut() {
+                *b = next(&mut seq)?;
+            }
+            Option::from(<C as Pairing>::PublicKey::from_bytes(&repr))
+                .ok_or_else(|| A::Error::custom("invalid signcrypt ciphertext u"))?
+        } else if first == SIGN_CRYPT_VERSION {
+            next::<PublicKeyField<C>, _>(&mut seq)?.0
+        } else {
+            return Err(A::Error::custom(BlsError::UnsupportedVersion(first)));
+        };
+        let v = next(&mut seq)?;
+        let w = next::<SignatureField<C>, _>(&mut seq)?.0;
+        let scheme = next(&mut seq)?;
+        let header = if legacy {
+            Vec::new()
+        } else {
+            read_header(&next::<Vec<u8>, _>(&mut seq)?)
+        };
+        Ok(SignCryptCiphertext {
+            u,
+            v,
+            w,
+            scheme,
+            header,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: BlsSignatureImpl> Serialize for SignCryptCiphertext<C> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        SignCryptEnvelope::<C> {
+            version: SIGN_CRYPT_VERSION,
+            u: self.u,
+            v: Cow::Borrowed(&self.v),
+            w: self.w,
+            scheme: self.scheme,
+            extensions: Cow::Owned(write_header(&self.header)),
+        }
+        .serialize(s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, C: BlsSignatureImpl> Deserialize<'de> for SignCryptCiphertext<C> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        if !d.is_human_readable() {
+            // At most the bytes of a legacy `u` followed by `v`, `w` and the scheme
+            let len = <<C as Pairing>::PublicKey as GroupEncoding>::Repr::default()
+                .as_ref()
+                .len()
+                + 3;
+            return d.deserialize_tuple(len, SignCryptVisitor(PhantomData));
+        }
+        let fields = SignCryptFields::<C>::deserialize(d)?;
+        match fields.version {
+            None | Some(SIGN_CRYPT_VERSION) => {}
+            Some(version) => {
+                return Err(D::Error::custom(BlsError::UnsupportedVersion(version)));
+            }
+        }
+        Ok(Self {
+            u: fields.u,
+            v: fields.v,
+            w: fields.w,
+            scheme: fields.scheme,
+            header: read_header(&fields.extensions),
+        })
+    }
+}
+
+/// Which parts of a serialized signcrypt ciphertext survived transport
+/// as reported by [`SignCryptCiphertext::check_integrity`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CiphertextIntegrity {
+    /// The version, `u`, `w` and scheme decoded and are consistent with `v`
+    pub header_ok: bool,
+    /// The `v` payload decoded and is authenticated by `w`
+    pub payload_ok: bool,
+}
+
+impl CiphertextIntegrity {
+    /// True if both the header and the payload are intact
+    pub fn is_intact(&self) -> bool {
+        self.header_ok && self.payload_ok
+    }
+}
+
+/// The extension area for `header`, a list of tagged entries.
+///
+/// Ciphertexts without a header keep an empty extension area
+fn write_header(header: &[u8]) -> Vec<u8> {
+    let mut w = codec::Writer::default();
+    if !header.is_empty() {
+        w.uint(1);
+        w.u8(HEADER_EXTENSION);
+        w.data(header);
+    }
+    w.into_bytes()
+}
+
+/// Find the header in an extension area.
+///
+/// Extensions that aren't a list of tagged entries are from a later revision
+/// of this version and are skipped along with unknown tags. Skipping a header
+/// isn't a concern since `w` no longer verifies without it
+fn read_header(extensions: &[u8]) -> Vec<u8> {
+    let mut r = codec::Reader::new(extensions);
+    let mut entries = Vec::new();
+    for _ in 0..r.uint().unwrap_or_default() {
+        match (r.u8(), r.data()) {
+            (Ok(tag), Ok(data)) => entries.push((tag, data)),
+            _ => return Vec::new(),
+        }
+    }
+    entries
+        .into_iter()
+        .find(|(tag, _)| *tag == HEADER_EXTENSION)
+        .map(|(_, header)| header.to_vec())
+        .unwrap_or_default()
 }
 
 impl<C: BlsSignatureImpl> Display for SignCryptCiphertext<C> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ u: {}, v: {:?}, w: {}, scheme: {:?} }}",
-            self.u, self.v, self.w, self.scheme
+            "{{ u: {}, v: {:?}, w: {}, scheme: {:?}, header: {:?} }}",
+            self.u, self.v, self.w, self.scheme, self.header
         )
     }
 }
 
 impl<C: BlsSignatureImpl> From<&SignCryptCiphertext<C>> for Vec<u8> {
     fn from(value: &SignCryptCiphertext<C>) -> Self {
-        serde_bare::to_vec(value).expect("failed to serialize SignCryptCiphertext")
+        codec::to_vec(value)
     }
 }
 
@@ -38,11 +304,58 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for SignCryptCiphertext<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> BlsResult<Self> {
-        let output = serde_bare::from_slice(value)?;
+        // Check the version before decoding the rest since a newer
+        // layout may not parse as this one
+        match value.first() {
+            Some(&SIGN_CRYPT_VERSION) => {}
+            Some(&first) if is_legacy(first) => {}
+            Some(&version) => return Err(BlsError::UnsupportedVersion(version)),
+            None => {
+                return Err(BlsError::DeserializationError(
+                    "empty signcrypt ciphertext".to_string(),
+                ))
+            }
+        }
+        let output = codec::from_slice(value)?;
         Ok(output)
     }
 }
 
+impl<C: BlsSignatureImpl> codec::Encode for SignCryptCiphertext<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.u8(SIGN_CRYPT_VERSION);
+        w.point(&self.u);
+        w.data(&self.v);
+        w.point(&self.w);
+        self.scheme.encode(w);
+        w.data(&write_header(&self.header));
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for SignCryptCiphertext<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        if is_legacy(r.peek()?) {
+            return Ok(Self {
+                u: r.point()?,
+                v: r.data()?.to_vec(),
+                w: r.point()?,
+                scheme: SignatureSchemes::decode(r)?,
+                header: Vec::new(),
+            });
+        }
+        if r.u8()? != SIGN_CRYPT_VERSION {
+            return Err(codec::DecodeError("unsupported version"));
+        }
+        Ok(Self {
+            u: r.point()?,
+            v: r.data()?.to_vec(),
+            w: r.point()?,
+            scheme: SignatureSchemes::decode(r)?,
+            header: read_header(r.data()?),
+        })
+    }
+}
+
 impl_from_derivatives_generic!(SignCryptCiphertext);
 
 impl<C: BlsSignatureImpl> SignCryptCiphertext<C> {
@@ -51,6 +364,11 @@ impl<C: BlsSignatureImpl> SignCryptCiphertext<C> {
         &self,
         sks: &SecretKeyShare<C>,
     ) -> BlsResult<SignDecryptionShare<C>> {
+        if self.u.is_identity().into() {
+            return Err(BlsError::InvalidInputs(
+                "invalid ciphertext. Contains an identity point".to_string(),
+            ));
+        }
         Ok(SignDecryptionShare(
             <C as BlsSignatureCore>::public_key_share_with_generator(&sks.0, self.u)?,
         ))
@@ -61,52 +379,179 @@ impl<C: BlsSignatureImpl> SignCryptCiphertext<C> {
         &self,
         shares: B,
     ) -> CtOption<Vec<u8>> {
-        let dst = match self.scheme {
-            SignatureSchemes::Basic => <C as BlsSignatureBasic>::DST,
-            SignatureSchemes::MessageAugmentation => <C as BlsSignatureMessageAugmentation>::DST,
-            SignatureSchemes::ProofOfPossession => <C as BlsSignaturePop>::SIG_DST,
-        };
+        let dst = sign_crypt_dst::<C>(self.scheme, &self.header);
 
         let shares = shares.as_ref().iter().map(|s| s.0).collect::<Vec<_>>();
-        <C as BlsSignCrypt>::unseal_with_shares(self.u, &self.v, self.w, shares.as_slice(), dst)
+        <C as BlsSignCrypt>::unseal_with_shares(self.u, &self.v, self.w, shares.as_slice(), &dst)
     }
 
     /// Decrypt the signcrypt ciphertext
     pub fn decrypt(&self, sk: &SecretKey<C>) -> CtOption<Vec<u8>> {
-        let dst = match self.scheme {
-            SignatureSchemes::Basic => <C as BlsSignatureBasic>::DST,
-            SignatureSchemes::MessageAugmentation => <C as BlsSignatureMessageAugmentation>::DST,
-            SignatureSchemes::ProofOfPossession => <C as BlsSignaturePop>::SIG_DST,
-        };
+        let dst = sign_crypt_dst::<C>(self.scheme, &self.header);
 
-        <C as BlsSignCrypt>::unseal(self.u, &self.v, self.w, &sk.0, dst)
+        <C as BlsSignCrypt>::unseal(self.u, &self.v, self.w, &sk.0, &dst)
+    }
+
+    /// Remove the `v` payload so it can be stored separately from the header.
+    ///
+    /// The returned bytes are passed to [`SignCryptCiphertext::decrypt_detached`]
+    /// and `self` is left with an empty `v`
+    pub fn detach_v(&mut self) -> Vec<u8> {
+        core::mem::take(&mut self.v)
+    }
+
+    /// Decrypt the signcrypt ciphertext using a `v` payload stored separately.
+    ///
+    /// Any `v` held by `self` is ignored and validity is checked against the
+    /// supplied payload
+    pub fn decrypt_detached(&self, sk: &SecretKey<C>, v: &[u8]) -> CtOption<Vec<u8>> {
+        let dst = sign_crypt_dst::<C>(self.scheme, &self.header);
+
+        <C as BlsSignCrypt>::unseal(self.u, v, self.w, &sk.0, &dst)
+    }
+
+    /// Decrypt the signcrypt ciphertext returning an error if it fails
+    pub fn try_decrypt(&self, sk: &SecretKey<C>) -> BlsResult<Vec<u8>> {
+        Option::from(self.decrypt(sk)).ok_or_else(|| {
+            BlsError::InvalidInputs("invalid signcrypt ciphertext".to_string())
+                .context("signcrypt unseal")
+        })
+    }
+
+    /// Try to decrypt the ciphertext with each key in `keys`.
+    ///
+    /// Returns the index of the first key that decrypts and the plaintext.
+    /// Every key is tried even after a match so the time taken doesn't
+    /// reveal which key succeeded.
+    ///
+    /// The ciphertext is not bound to its recipient so, like [`Self::decrypt`],
+    /// a wrong key can occasionally unseal to garbage. Callers that need to
+    /// know the plaintext is authentic should check it at a higher layer
+    pub fn try_decrypt_with_keyring(&self, keys: &[SecretKey<C>]) -> Option<(usize, Vec<u8>)> {
+        let mut result = None;
+        for (i, key) in keys.iter().enumerate() {
+            let plaintext = Option::<Vec<u8>>::from(self.decrypt(key));
+            if result.is_none() {
+                result = plaintext.map(|p| (i, p));
+            }
+        }
+        result
+    }
+
+    /// Open the ciphertext given the decryption shares returning an error if it fails
+    pub fn try_decrypt_with_shares<B: AsRef<[SignDecryptionShare<C>]>>(
+        &self,
+        shares: B,
+    ) -> BlsResult<Vec<u8>> {
+        Option::from(self.decrypt_with_shares(shares)).ok_or_else(|| {
+            BlsError::InvalidInputs("invalid signcrypt ciphertext or shares".to_string())
+                .context("signcrypt unseal")
+        })
     }
 
     /// Check if the ciphertext is valid
     pub fn is_valid(&self) -> Choice {
-        match self.scheme {
-            SignatureSchemes::Basic => {
-                <C as BlsSignCrypt>::valid(self.u, &self.v, self.w, <C as BlsSignatureBasic>::DST)
+        let dst = sign_crypt_dst::<C>(self.scheme, &self.header);
+        <C as BlsSignCrypt>::valid(self.u, &self.v, self.w, &dst)
+    }
+
+    /// Check a serialized ciphertext and report whether the header
+    /// (version, `u`, `w` and scheme) or the `v` payload was corrupted.
+    ///
+    /// `u` and `w` are curve points so corrupting their bytes almost always
+    /// stops them decoding. When the header decodes but `w` doesn't
+    /// authenticate `v` under the encoded scheme, the other schemes are tried
+    /// to tell a corrupted scheme byte from a corrupted payload. When `u` or
+    /// `w` can't be decoded the payload can't be authenticated either so
+    /// neither part is reported as ok. An authenticated header that was
+    /// altered can't be told apart from a corrupted payload.
+    ///
+    /// This is intended for diagnosing transport errors and is not constant time.
+    pub fn check_integrity(bytes: &[u8]) -> CiphertextIntegrity {
+        const SCHEMES: [SignatureSchemes; 3] = [
+            SignatureSchemes::Basic,
+            SignatureSchemes::MessageAugmentation,
+            SignatureSchemes::ProofOfPossession,
+        ];
+
+        // Decode each field in turn so a failure can be attributed to it
+        let mut reader = codec::Reader::new(bytes);
+        let version_ok = reader.u8().ok() == Some(SIGN_CRYPT_VERSION);
+        let u = match reader.point::<<C as Pairing>::PublicKey>() {
+            Ok(u) if !bool::from(u.is_identity()) => u,
+            _ => return CiphertextIntegrity::default(),
+        };
+        let v = match reader.data() {
+            Ok(v) if v.len() >= 32 => v,
+            // The rest of the header can't be located without the payload length
+            _ => {
+                return CiphertextIntegrity {
+                    header_ok: version_ok,
+                    payload_ok: false,
+                }
             }
-            SignatureSchemes::MessageAugmentation => <C as BlsSignCrypt>::valid(
-                self.u,
-                &self.v,
-                self.w,
-                <C as BlsSignatureMessageAugmentation>::DST,
-            ),
-            SignatureSchemes::ProofOfPossession => {
-                <C as BlsSignCrypt>::valid(self.u, &self.v, self.w, <C as BlsSignaturePop>::SIG_DST)
+        };
+        let w = match reader.point::<<C as Pairing>::Signature>() {
+            Ok(w) if !bool::from(w.is_identity()) => w,
+            _ => return CiphertextIntegrity::default(),
+        };
+        let scheme = reader
+            .u8()
+            .ok()
+            .and_then(|s| SCHEMES.iter().copied().find(|scheme| *scheme as u8 == s));
+        let extensions = reader.data();
+        let structure_ok =
+            version_ok && scheme.is_some() && extensions.is_ok() && reader.is_empty();
+        let header = extensions.map(read_header).unwrap_or_default();
+        let dst = |scheme: SignatureSchemes| sign_crypt_dst::<C>(scheme, &header);
+
+        if let Some(scheme) = scheme {
+            if <C as BlsSignCrypt>::valid(u, v, w, &dst(scheme)).into() {
+                return CiphertextIntegrity {
+                    header_ok: structure_ok,
+                    payload_ok: true,
+                };
             }
         }
+        let other_scheme = SCHEMES
+            .iter()
+            .filter(|s| Some(**s) != scheme)
+            .any(|s| <C as BlsSignCrypt>::valid(u, v, w, &dst(*s)).into());
+        CiphertextIntegrity {
+            header_ok: structure_ok && !other_scheme,
+            payload_ok: other_scheme,
+        }
+    }
+
+    /// Check the ciphertext is valid and return a routing tag derived from `u`.
+    ///
+    /// The tag is `SHA-256(dst || u)` and doesn't require the decryption key
+    /// so intermediaries can reject malformed ciphertexts and route valid ones.
+    pub fn verify_and_extract_ephemeral(&self) -> BlsResult<[u8; 32]> {
+        if !bool::from(self.is_valid()) {
+            return Err(BlsError::InvalidInputs(
+                "invalid signcrypt ciphertext".to_string(),
+            ));
+        }
+        let mut hasher = Sha256::new();
+        hasher.update(ROUTING_DST);
+        hasher.update(self.u.to_bytes().as_ref());
+        Ok(hasher.finalize().into())
     }
 }
 
 /// A Signcrypt decryption key where the secret key is hidden or combined from shares
 /// that can decrypt ciphertext
-#[derive(Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SignCryptDecryptionKey<C: BlsSignatureImpl>(
-    #[serde(serialize_with = "traits::public_key::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::public_key::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::public_key::serialize::<C, _>",
+            deserialize_with = "traits::public_key::deserialize::<C, _>"
+        )
+    )]
     pub <C as Pairing>::PublicKey,
 );
 
@@ -124,7 +569,7 @@ impl<C: BlsSignatureImpl> Clone for SignCryptDecryptionKey<C> {
 
 impl<C: BlsSignatureImpl> From<&SignCryptDecryptionKey<C>> for Vec<u8> {
     fn from(value: &SignCryptDecryptionKey<C>) -> Self {
-        serde_bare::to_vec(value).expect("failed to serialize SignCryptDecryptionKey")
+        codec::to_vec(value)
     }
 }
 
@@ -132,23 +577,30 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for SignCryptDecryptionKey<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> BlsResult<Self> {
-        let output = serde_bare::from_slice(value)?;
+        let output = codec::from_slice(value)?;
         Ok(output)
     }
 }
 
+impl<C: BlsSignatureImpl> codec::Encode for SignCryptDecryptionKey<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.point(&self.0);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for SignCryptDecryptionKey<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        r.point().map(Self)
+    }
+}
+
 impl_from_derivatives_generic!(SignCryptDecryptionKey);
 
 impl<C: BlsSignatureImpl> SignCryptDecryptionKey<C> {
     /// Decrypt signcrypt ciphertext
     pub fn decrypt(&self, ciphertext: &SignCryptCiphertext<C>) -> CtOption<Vec<u8>> {
-        let dst = match ciphertext.scheme {
-            SignatureSchemes::Basic => <C as BlsSignatureBasic>::DST,
-            SignatureSchemes::MessageAugmentation => <C as BlsSignatureMessageAugmentation>::DST,
-            SignatureSchemes::ProofOfPossession => <C as BlsSignaturePop>::SIG_DST,
-        };
-
-        let choice = <C as BlsSignCrypt>::valid(ciphertext.u, &ciphertext.v, ciphertext.w, dst);
+        let dst = sign_crypt_dst::<C>(ciphertext.scheme, &ciphertext.header);
+        let choice = <C as BlsSignCrypt>::valid(ciphertext.u, &ciphertext.v, ciphertext.w, &dst);
         <C as BlsSignCrypt>::decrypt(&ciphertext.v, self.0, choice)
     }
 
@@ -158,6 +610,33 @@ impl<C: BlsSignatureImpl> SignCryptDecryptionKey<C> {
             .iter()
             .map(|s| s.0)
             .collect::<Vec<<C as Pairing>::PublicKeyShare>>();
-        <C as BlsSignatureCore>::core_combine_public_key_shares(&points).map(Self)
+        <C as BlsSignatureCore>::core_combine_public_key_shares(&points)
+            .map(Self)
+            .context("signcrypt decryption key combination")
+    }
+
+    /// Combine decryption shares into a signcrypt decryption key after checking
+    /// there are at least `config.threshold` shares and every share identifier
+    /// is no greater than `config.limit`
+    pub fn from_shares_with_config(
+        shares: &[SignDecryptionShare<C>],
+        config: ThresholdConfig,
+    ) -> BlsResult<Self> {
+        let points = shares
+            .iter()
+            .map(|s| s.0)
+            .collect::<Vec<<C as Pairing>::PublicKeyShare>>();
+        config.check_shares(&points)?;
+        <C as BlsSignatureCore>::core_combine_public_key_shares(&points)
+            .map(Self)
+            .context("signcrypt decryption key combination")
+    }
+
+    /// The wrapped point, the ciphertext's `U` value multiplied by the secret key.
+    ///
+    /// This is secret: anyone holding it can open the ciphertext it was
+    /// created from. It isn't a public key and won't verify signatures
+    pub fn as_raw_point(&self) -> &<C as Pairing>::PublicKey {
+        &self.0
     }
 }
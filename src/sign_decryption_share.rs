@@ -1,14 +1,19 @@
 use crate::*;
 
-/// A public key share is point on the curve.
+/// A share of a signcrypt decryption key.
 ///
-/// See Section 4.3 in
-/// <https://eprint.iacr.org/2016/663.pdf>
-/// Must be combined with other public key shares
-/// to produce the completed key, or used for
-/// creating partial signatures which can be
-/// combined into a complete signature
-#[derive(PartialEq, Eq, Serialize, Deserialize)]
+/// The share is the secret key share times the ciphertext's `u`, a point
+/// in the public key group, created with
+/// [`SignCryptCiphertext::create_decryption_share`]. Threshold many shares
+/// open the ciphertext with [`SignCryptCiphertext::decrypt_with_shares`] or
+/// combine with [`SignCryptDecryptionKey::from_shares`].
+///
+/// It is not a [`SignatureShare`], which is in the signature group, and a
+/// signature share over any message can't be used in its place.
+///
+/// It has the same stable serde layout as a [`PublicKeyShare`].
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SignDecryptionShare<C: BlsSignatureImpl>(pub <C as Pairing>::PublicKeyShare);
 
 impl<C: BlsSignatureImpl> Clone for SignDecryptionShare<C> {
@@ -25,19 +30,30 @@ impl<C: BlsSignatureImpl> fmt::Debug for SignDecryptionShare<C> {
 
 impl<C: BlsSignatureImpl> From<&SignDecryptionShare<C>> for Vec<u8> {
     fn from(share: &SignDecryptionShare<C>) -> Vec<u8> {
-        serde_bare::to_vec(&share.0).unwrap()
+        codec::encode(share)
     }
 }
 
 impl<C: BlsSignatureImpl> TryFrom<&[u8]> for SignDecryptionShare<C> {
     type Error = BlsError;
     fn try_from(bytes: &[u8]) -> BlsResult<Self> {
-        serde_bare::from_slice(bytes)
-            .map(Self)
+        codec::decode(bytes)
             .map_err(|_| BlsError::InvalidInputs("invalid byte sequence".to_string()))
     }
 }
 
+impl<C: BlsSignatureImpl> codec::Encode for SignDecryptionShare<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.point_share(&self.0);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for SignDecryptionShare<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        r.point_share().map(Self)
+    }
+}
+
 impl_from_derivatives_generic!(SignDecryptionShare);
 
 impl<C: BlsSignatureImpl> SignDecryptionShare<C> {
@@ -45,16 +61,8 @@ impl<C: BlsSignatureImpl> SignDecryptionShare<C> {
     pub fn verify(&self, pks: &PublicKeyShare<C>, sig: &SignCryptCiphertext<C>) -> BlsResult<()> {
         let share = *self.0.value();
         let pk = *pks.0.value();
-        if <C as BlsSignCrypt>::verify_share(
-            share.0,
-            pk.0,
-            sig.u,
-            &sig.v,
-            sig.w,
-            <C as BlsSignatureBasic>::DST,
-        )
-        .into()
-        {
+        let dst = sign_crypt_dst::<C>(sig.scheme, &sig.header);
+        if <C as BlsSignCrypt>::verify_share(share.0, pk.0, sig.u, &sig.v, sig.w, &dst).into() {
             Ok(())
         } else {
             Err(BlsError::InvalidDecryptionShare)
@@ -2,24 +2,40 @@ use crate::*;
 use subtle::ConditionallySelectable;
 
 /// A BLS signature wrapped in the appropriate scheme used to generate it
-#[derive(PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Signature<C: BlsSignatureImpl> {
     /// The basic signature scheme
     Basic(
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
     /// The message augmentation signature scheme
     MessageAugmentation(
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
     /// The proof of possession scheme
     ProofOfPossession(
-        #[serde(serialize_with = "traits::signature::serialize::<C, _>")]
-        #[serde(deserialize_with = "traits::signature::deserialize::<C, _>")]
+        #[cfg_attr(
+            feature = "serde",
+            serde(
+                serialize_with = "traits::signature::serialize::<C, _>",
+                deserialize_with = "traits::signature::deserialize::<C, _>"
+            )
+        )]
         <C as Pairing>::Signature,
     ),
 }
@@ -81,7 +97,7 @@ impl_from_derivatives_generic!(Signature);
 
 impl<C: BlsSignatureImpl> From<&Signature<C>> for Vec<u8> {
     fn from(value: &Signature<C>) -> Self {
-        serde_bare::to_vec(value).unwrap()
+        codec::to_vec(value)
     }
 }
 
@@ -89,20 +105,114 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for Signature<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        serde_bare::from_slice(value).map_err(|e| BlsError::InvalidInputs(e.to_string()))
+        codec::from_slice(value).map_err(|e| BlsError::InvalidInputs(e.to_string()))
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Encode for Signature<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        let (variant, sig) = match self {
+            Self::Basic(sig) => (0, sig),
+            Self::MessageAugmentation(sig) => (1, sig),
+            Self::ProofOfPossession(sig) => (2, sig),
+        };
+        w.uint(variant);
+        w.point(sig);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for Signature<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        match r.uint()? {
+            0 => r.point().map(Self::Basic),
+            1 => r.point().map(Self::MessageAugmentation),
+            2 => r.point().map(Self::ProofOfPossession),
+            _ => Err(codec::DecodeError("unknown signature scheme")),
+        }
     }
 }
 
 impl<C: BlsSignatureImpl> Signature<C> {
     /// Verify the signature using the public key
+    ///
+    /// As a deliberate security default the identity signature is rejected with
+    /// [`BlsError::InvalidSignature`] and the identity public key with
+    /// [`BlsError::InvalidPublicKey`] before any pairing is computed, since
+    /// either can be used to forge a signature that trivially verifies
     pub fn verify<B: AsRef<[u8]>>(&self, pk: &PublicKey<C>, msg: B) -> BlsResult<()> {
-        match self {
-            Self::Basic(sig) => <C as BlsSignatureBasic>::verify(pk.0, *sig, msg),
-            Self::MessageAugmentation(sig) => {
-                <C as BlsSignatureMessageAugmentation>::verify(pk.0, *sig, msg)
+        traced!("bls.verify", { scheme = %self.scheme(), msg_len = msg.as_ref().len() }, {
+            match self {
+                Self::Basic(sig) => <C as BlsSignatureBasic>::verify(pk.0, *sig, msg),
+                Self::MessageAugmentation(sig) => {
+                    <C as BlsSignatureMessageAugmentation>::verify(pk.0, *sig, msg)
+                }
+                Self::ProofOfPossession(sig) => <C as BlsSignaturePop>::verify(pk.0, *sig, msg),
             }
-            Self::ProofOfPossession(sig) => <C as BlsSignaturePop>::verify(pk.0, *sig, msg),
+        })
+    }
+
+    /// Verify a signature against the hash to curve point of the message
+    ///
+    /// The point must be computed the same way as the signing scheme, e.g. as
+    /// returned by [`SecretKey::sign_returning_hash`]
+    pub fn verify_point(
+        &self,
+        pk: &PublicKey<C>,
+        hash: <C as Pairing>::Signature,
+    ) -> BlsResult<()> {
+        <C as BlsSignatureCore>::core_verify_point(pk.0, *self.as_raw_value(), hash)
+    }
+
+    /// Verify a signature over a message provided as multiple segments.
+    ///
+    /// This is the counterpart to [`SecretKey::sign_segments`] and is
+    /// identical to verifying the concatenation of `segments`
+    pub fn verify_segments(&self, pk: &PublicKey<C>, segments: &[&[u8]]) -> BlsResult<()> {
+        self.verify(pk, segments.concat())
+    }
+
+    /// Verify a signature over EIP-712 style structured data.
+    ///
+    /// This is the counterpart to [`SecretKey::sign_structured`]
+    pub fn verify_structured(
+        &self,
+        pk: &PublicKey<C>,
+        domain_separator: &[u8; 32],
+        struct_hash: &[u8; 32],
+    ) -> BlsResult<()> {
+        self.verify(pk, structured_message(domain_separator, struct_hash))
+    }
+
+    /// Verify a signature over a precomputed 32 byte digest.
+    ///
+    /// This is the counterpart to [`SecretKey::sign_digest`] and won't accept
+    /// a signature made with [`SecretKey::sign`] over the digest bytes
+    pub fn verify_digest(&self, pk: &PublicKey<C>, digest: &[u8; 32]) -> BlsResult<()> {
+        self.verify(pk, digest_message(digest))
+    }
+
+    /// Verify a proof of possession signature over `msg` by every key in `pks`.
+    ///
+    /// This is `FastAggregateVerify` from the IETF draft as used by Ethereum
+    /// consensus clients. The public keys are aggregated internally, an empty
+    /// key set fails with [`BlsError::EmptyAggregate`] and the identity public
+    /// key fails with [`BlsError::InvalidPublicKey`]
+    pub fn fast_aggregate_verify<B: AsRef<[u8]>>(
+        &self,
+        pks: &[PublicKey<C>],
+        msg: B,
+    ) -> BlsResult<()> {
+        let sig = match self {
+            Self::ProofOfPossession(sig) => *sig,
+            _ => return Err(BlsError::InvalidSignatureScheme),
+        };
+        if pks.is_empty() {
+            return Err(BlsError::EmptyAggregate);
+        }
+        if pks.iter().any(|pk| pk.0.is_identity().into()) {
+            return Err(BlsError::InvalidPublicKey);
         }
+        <C as BlsSignaturePop>::multi_sig_verify(pks.iter().map(|pk| pk.0), sig, msg)
     }
 
     /// Determine if two signature were signed using the same scheme
@@ -116,19 +226,65 @@ impl<C: BlsSignatureImpl> Signature<C> {
     }
 
     /// Create a signature from shares
+    ///
+    /// Any number of shares at or above the threshold can be supplied. All of
+    /// them are used to interpolate the signature, which gives the same result
+    /// as using exactly `threshold` shares when every share is valid.
     pub fn from_shares(shares: &[SignatureShare<C>]) -> BlsResult<Self> {
-        if !shares.iter().skip(1).all(|s| s.same_scheme(&shares[0])) {
-            return Err(BlsError::InvalidSignatureScheme);
+        traced!("bls.combine_signature_shares", {
+            scheme = ?shares.first().map(SignatureShare::scheme),
+            shares = shares.len()
+        }, {
+            if shares.is_empty() {
+                return Err(BlsError::InvalidInputs("no shares provided".to_string()));
+            }
+            if !shares.iter().skip(1).all(|s| s.same_scheme(&shares[0])) {
+                return Err(BlsError::InvalidSignatureScheme);
+            }
+            let points = shares
+                .iter()
+                .map(|s| *s.as_raw_value())
+                .collect::<Vec<<C as Pairing>::SignatureShare>>();
+            let sig = <C as BlsSignatureCore>::core_combine_signature_shares(&points)
+                .context("signature share combination")?;
+            match shares[0] {
+                SignatureShare::Basic(_) => Ok(Self::Basic(sig)),
+                SignatureShare::MessageAugmentation(_) => Ok(Self::MessageAugmentation(sig)),
+                SignatureShare::ProofOfPossession(_) => Ok(Self::ProofOfPossession(sig)),
+            }
+        })
+    }
+
+    /// Create a signature from shares and check it against the group public key.
+    ///
+    /// Fails if the interpolated signature does not verify for `msg`,
+    /// which happens when any of the supplied shares is invalid
+    pub fn from_shares_verified<B: AsRef<[u8]>>(
+        shares: &[SignatureShare<C>],
+        pk: &PublicKey<C>,
+        msg: B,
+    ) -> BlsResult<Self> {
+        let sig = Self::from_shares(shares)?;
+        sig.verify(pk, msg)
+            .context("combined signature verification")?;
+        Ok(sig)
+    }
+
+    /// Wrap a raw signature point created with `scheme`
+    pub(crate) fn from_raw(scheme: SignatureSchemes, sig: <C as Pairing>::Signature) -> Self {
+        match scheme {
+            SignatureSchemes::Basic => Self::Basic(sig),
+            SignatureSchemes::MessageAugmentation => Self::MessageAugmentation(sig),
+            SignatureSchemes::ProofOfPossession => Self::ProofOfPossession(sig),
         }
-        let points = shares
-            .iter()
-            .map(|s| *s.as_raw_value())
-            .collect::<Vec<<C as Pairing>::SignatureShare>>();
-        let sig = <C as BlsSignatureCore>::core_combine_signature_shares(&points)?;
-        match shares[0] {
-            SignatureShare::Basic(_) => Ok(Self::Basic(sig)),
-            SignatureShare::MessageAugmentation(_) => Ok(Self::MessageAugmentation(sig)),
-            SignatureShare::ProofOfPossession(_) => Ok(Self::ProofOfPossession(sig)),
+    }
+
+    /// The signature scheme used to create this signature
+    pub fn scheme(&self) -> SignatureSchemes {
+        match self {
+            Self::Basic(_) => SignatureSchemes::Basic,
+            Self::MessageAugmentation(_) => SignatureSchemes::MessageAugmentation,
+            Self::ProofOfPossession(_) => SignatureSchemes::ProofOfPossession,
         }
     }
 
@@ -140,6 +296,37 @@ impl<C: BlsSignatureImpl> Signature<C> {
             Self::ProofOfPossession(s) => s,
         }
     }
+
+    /// Convert this signature into the scheme byte followed by the compressed point.
+    ///
+    /// This is the same format accepted by `TryFrom<Vec<u8>>`
+    pub fn into_vec(self) -> Vec<u8> {
+        Vec::from(&self)
+    }
+
+    /// Encode this signature as a self describing blob.
+    ///
+    /// This is the same encoding as `Vec::from(&Signature)`: the
+    /// [`SignatureSchemes`] byte followed by the compressed signature point,
+    /// so a verifier can determine the scheme from the blob alone
+    pub fn to_tagged_bytes(&self) -> Vec<u8> {
+        Vec::from(self)
+    }
+
+    /// Decode a blob created by [`Signature::to_tagged_bytes`]
+    /// returning the scheme encoded in the first byte and the signature.
+    ///
+    /// Unknown scheme bytes are rejected with [`BlsError::InvalidSignatureScheme`]
+    pub fn from_tagged_bytes(bytes: &[u8]) -> BlsResult<(SignatureSchemes, Self)> {
+        match bytes.first() {
+            None => Err(BlsError::InvalidInputs("invalid byte sequence".to_string())),
+            Some(0..=2) => {
+                let sig = Self::try_from(bytes)?;
+                Ok((sig.scheme(), sig))
+            }
+            Some(_) => Err(BlsError::InvalidSignatureScheme),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -183,4 +370,32 @@ mod tests {
         assert!(res_sig_pop2.is_ok());
         assert_eq!(sig_pop, res_sig_pop2.unwrap());
     }
+
+    #[rstest]
+    #[case::g1(Bls12381G1Impl, 49)]
+    #[case::g2(Bls12381G2Impl, 97)]
+    fn into_vec<C: BlsSignatureImpl + PartialEq + Eq + fmt::Debug>(
+        #[case] _c: C,
+        #[case] expected_len: usize,
+    ) {
+        const TEST_MSG: &[u8] = b"test_into_vec";
+
+        let sk = SecretKey::<C>::from_hash(TEST_MSG);
+        for scheme in [
+            SignatureSchemes::Basic,
+            SignatureSchemes::MessageAugmentation,
+            SignatureSchemes::ProofOfPossession,
+        ] {
+            let sig = sk.sign(scheme, TEST_MSG).unwrap();
+            let bytes = sig.into_vec();
+            assert_eq!(bytes.len(), expected_len);
+            assert_eq!(bytes[0], scheme as u8);
+            assert_eq!(&bytes[1..], sig.as_raw_value().to_bytes().as_ref());
+
+            let sig2 = Signature::<C>::try_from(bytes).unwrap();
+            assert_eq!(sig, sig2);
+        }
+
+        assert!(Signature::<C>::try_from(vec![0u8; expected_len - 1]).is_err());
+    }
 }
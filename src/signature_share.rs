@@ -1,7 +1,15 @@
 use crate::*;
+use rand_core::{CryptoRng, RngCore};
 
 /// Represents a share of a signature
-#[derive(PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+///
+/// A share is the secret key share times the message hashed to the
+/// signature group and combines with [`Signature::from_shares`].
+/// It is not a [`SignDecryptionShare`], which is the secret key share times
+/// a signcrypt ciphertext's `u` in the public key group. The two live in
+/// different groups so neither can be converted into the other
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SignatureShare<C: BlsSignatureImpl> {
     /// The basic signature scheme
     Basic(<C as Pairing>::SignatureShare),
@@ -68,15 +76,7 @@ impl_from_derivatives_generic!(SignatureShare);
 
 impl<C: BlsSignatureImpl> From<&SignatureShare<C>> for Vec<u8> {
     fn from(s: &SignatureShare<C>) -> Self {
-        match s {
-            SignatureShare::Basic(s) => serde_bare::to_vec(&(SignatureSchemes::Basic, s)).unwrap(),
-            SignatureShare::MessageAugmentation(s) => {
-                serde_bare::to_vec(&(SignatureSchemes::MessageAugmentation, s)).unwrap()
-            }
-            SignatureShare::ProofOfPossession(s) => {
-                serde_bare::to_vec(&(SignatureSchemes::ProofOfPossession, s)).unwrap()
-            }
-        }
+        codec::encode(s)
     }
 }
 
@@ -84,9 +84,28 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for SignatureShare<C> {
     type Error = BlsError;
 
     fn try_from(bytes: &[u8]) -> BlsResult<Self> {
-        let (scheme, s): (SignatureSchemes, <C as Pairing>::SignatureShare) =
-            serde_bare::from_slice(bytes)
-                .map_err(|_| BlsError::InvalidInputs("invalid byte sequence".to_string()))?;
+        codec::decode(bytes)
+            .map_err(|_| BlsError::InvalidInputs("invalid byte sequence".to_string()))
+    }
+}
+
+/// The scheme byte followed by the share
+impl<C: BlsSignatureImpl> codec::Encode for SignatureShare<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        let (scheme, share) = match self {
+            Self::Basic(s) => (SignatureSchemes::Basic, s),
+            Self::MessageAugmentation(s) => (SignatureSchemes::MessageAugmentation, s),
+            Self::ProofOfPossession(s) => (SignatureSchemes::ProofOfPossession, s),
+        };
+        scheme.encode(w);
+        w.point_share(share);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for SignatureShare<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        let scheme = SignatureSchemes::decode(r)?;
+        let s = r.point_share()?;
         match scheme {
             SignatureSchemes::Basic => Ok(Self::Basic(s)),
             SignatureSchemes::MessageAugmentation => Ok(Self::MessageAugmentation(s)),
@@ -111,6 +130,15 @@ impl<C: BlsSignatureImpl> SignatureShare<C> {
         )
     }
 
+    /// The signature scheme used to create this signature share
+    pub fn scheme(&self) -> SignatureSchemes {
+        match self {
+            Self::Basic(_) => SignatureSchemes::Basic,
+            Self::MessageAugmentation(_) => SignatureSchemes::MessageAugmentation,
+            Self::ProofOfPossession(_) => SignatureSchemes::ProofOfPossession,
+        }
+    }
+
     /// Extract the inner raw representation
     pub fn as_raw_value(&self) -> &<C as Pairing>::SignatureShare {
         match self {
@@ -120,6 +148,39 @@ impl<C: BlsSignatureImpl> SignatureShare<C> {
         }
     }
 
+    /// Prove this share is `msg` signed with `sks`.
+    ///
+    /// The proof can be checked against the signer's public key share with
+    /// [`SignatureShare::verify_share_correctness`] without computing a pairing
+    pub fn prove_correctness<B: AsRef<[u8]>>(
+        &self,
+        sks: &SecretKeyShare<C>,
+        msg: B,
+    ) -> BlsResult<ShareCorrectnessProof<C>> {
+        self.prove_correctness_with_rng(sks, msg, get_crypto_rng())
+    }
+
+    /// Prove this share is `msg` signed with `sks` using the supplied rng
+    pub fn prove_correctness_with_rng<B: AsRef<[u8]>>(
+        &self,
+        sks: &SecretKeyShare<C>,
+        msg: B,
+        rng: impl RngCore + CryptoRng,
+    ) -> BlsResult<ShareCorrectnessProof<C>> {
+        ShareCorrectnessProof::generate(self, sks, msg.as_ref(), rng)
+    }
+
+    /// Check a proof that this share is `msg` signed with the secret key share
+    /// behind `pks`
+    pub fn verify_share_correctness<B: AsRef<[u8]>>(
+        &self,
+        pks: &PublicKeyShare<C>,
+        msg: B,
+        proof: &ShareCorrectnessProof<C>,
+    ) -> BlsResult<()> {
+        proof.verify(self, pks, msg.as_ref())
+    }
+
     /// Convert a share byte sequence from version 1 to a signature share
     /// that was output from converting to Vec<u8>
     pub fn from_v1_inner_bytes(raw_bytes: &[u8]) -> BlsResult<Self> {
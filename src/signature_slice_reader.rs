@@ -0,0 +1,71 @@
+use crate::*;
+use core::marker::PhantomData;
+
+/// Reads signatures from a buffer of contiguous compressed points
+/// without copying the buffer.
+///
+/// Each chunk is decoded and checked for subgroup membership only when
+/// it is reached. A trailing chunk that is too short yields a single error
+/// and ends the iteration.
+#[derive(Debug, Clone)]
+pub struct SignatureSliceReader<'a, C: BlsSignatureImpl> {
+    bytes: &'a [u8],
+    scheme: SignatureSchemes,
+    _marker: PhantomData<C>,
+}
+
+impl<'a, C: BlsSignatureImpl> SignatureSliceReader<'a, C> {
+    /// Create a new reader where every signature was created with `scheme`
+    pub fn new(bytes: &'a [u8], scheme: SignatureSchemes) -> Self {
+        Self {
+            bytes,
+            scheme,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The length in bytes of each compressed signature
+    pub fn chunk_size() -> usize {
+        <C::Signature as GroupEncoding>::Repr::default()
+            .as_ref()
+            .len()
+    }
+}
+
+impl<C: BlsSignatureImpl> Iterator for SignatureSliceReader<'_, C> {
+    type Item = BlsResult<Signature<C>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let size = Self::chunk_size();
+        if self.bytes.len() < size {
+            let remaining = self.bytes.len();
+            self.bytes = &[];
+            return Some(Err(BlsError::InvalidInputs(format!(
+                "truncated signature, expected {} bytes but found {}",
+                size, remaining
+            ))));
+        }
+        let (chunk, rest) = self.bytes.split_at(size);
+        self.bytes = rest;
+
+        let mut repr = <C::Signature as GroupEncoding>::Repr::default();
+        repr.as_mut().copy_from_slice(chunk);
+        let sig = match Option::<C::Signature>::from(C::Signature::from_bytes(&repr)) {
+            Some(sig) => sig,
+            None => return Some(Err(BlsError::InvalidSignature)),
+        };
+        Some(Ok(Signature::from_raw(self.scheme, sig)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.bytes.len().div_ceil(Self::chunk_size());
+        (n, Some(n))
+    }
+}
+
+impl<C: BlsSignatureImpl> ExactSizeIterator for SignatureSliceReader<'_, C> {}
+
+impl<C: BlsSignatureImpl> core::iter::FusedIterator for SignatureSliceReader<'_, C> {}
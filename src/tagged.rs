@@ -0,0 +1,153 @@
+use crate::*;
+use serde::de::Error as _;
+
+/// A public key that serializes as `{"curve": "bls12_381_g1", "key": "0x..."}`
+///
+/// The curve name is checked against `C` when deserializing so keys for one
+/// implementation can't be mistaken for another. This is intended for
+/// self-describing formats like JSON.
+#[derive(Default, PartialEq, Eq)]
+pub struct TaggedPublicKey<C: BlsSignatureImpl>(pub PublicKey<C>);
+
+/// A signature that serializes as `{"curve": "bls12_381_g1", "signature": "0x..."}`
+///
+/// The curve name is checked against `C` when deserializing so signatures for one
+/// implementation can't be mistaken for another. This is intended for
+/// self-describing formats like JSON.
+#[derive(Default, PartialEq, Eq)]
+pub struct TaggedSignature<C: BlsSignatureImpl>(pub Signature<C>);
+
+#[derive(Serialize, Deserialize)]
+struct TaggedPublicKeyRepr {
+    curve: String,
+    key: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TaggedSignatureRepr {
+    curve: String,
+    signature: String,
+}
+
+impl<C: BlsSignatureImpl> fmt::Debug for TaggedPublicKey<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TaggedPublicKey{{ curve: {}, key: {:?} }}",
+            C::NAME,
+            self.0
+        )
+    }
+}
+
+impl<C: BlsSignatureImpl> Copy for TaggedPublicKey<C> {}
+
+impl<C: BlsSignatureImpl> Clone for TaggedPublicKey<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: BlsSignatureImpl> From<PublicKey<C>> for TaggedPublicKey<C> {
+    fn from(pk: PublicKey<C>) -> Self {
+        Self(pk)
+    }
+}
+
+impl<C: BlsSignatureImpl> From<TaggedPublicKey<C>> for PublicKey<C> {
+    fn from(pk: TaggedPublicKey<C>) -> Self {
+        pk.0
+    }
+}
+
+impl<C: BlsSignatureImpl> Serialize for TaggedPublicKey<C> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        TaggedPublicKeyRepr {
+            curve: C::NAME.to_string(),
+            key: to_prefixed_hex(&Vec::from(&self.0)),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de, C: BlsSignatureImpl> Deserialize<'de> for TaggedPublicKey<C> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let repr = TaggedPublicKeyRepr::deserialize(d)?;
+        check_curve::<C, D::Error>(&repr.curve)?;
+        let bytes = from_prefixed_hex(&repr.key).map_err(D::Error::custom)?;
+        PublicKey::try_from(bytes)
+            .map(Self)
+            .map_err(D::Error::custom)
+    }
+}
+
+impl<C: BlsSignatureImpl> fmt::Debug for TaggedSignature<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TaggedSignature{{ curve: {}, signature: {:?} }}",
+            C::NAME,
+            self.0
+        )
+    }
+}
+
+impl<C: BlsSignatureImpl> Copy for TaggedSignature<C> {}
+
+impl<C: BlsSignatureImpl> Clone for TaggedSignature<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C: BlsSignatureImpl> From<Signature<C>> for TaggedSignature<C> {
+    fn from(sig: Signature<C>) -> Self {
+        Self(sig)
+    }
+}
+
+impl<C: BlsSignatureImpl> From<TaggedSignature<C>> for Signature<C> {
+    fn from(sig: TaggedSignature<C>) -> Self {
+        sig.0
+    }
+}
+
+impl<C: BlsSignatureImpl> Serialize for TaggedSignature<C> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        TaggedSignatureRepr {
+            curve: C::NAME.to_string(),
+            signature: to_prefixed_hex(&Vec::from(&self.0)),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de, C: BlsSignatureImpl> Deserialize<'de> for TaggedSignature<C> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let repr = TaggedSignatureRepr::deserialize(d)?;
+        check_curve::<C, D::Error>(&repr.curve)?;
+        let bytes = from_prefixed_hex(&repr.signature).map_err(D::Error::custom)?;
+        Signature::try_from(bytes)
+            .map(Self)
+            .map_err(D::Error::custom)
+    }
+}
+
+fn check_curve<C: BlsSignatureImpl, E: serde::de::Error>(curve: &str) -> Result<(), E> {
+    if curve != C::NAME {
+        return Err(E::custom(format!(
+            "invalid curve, expected {}, got {}",
+            C::NAME,
+            curve
+        )));
+    }
+    Ok(())
+}
+
+fn to_prefixed_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn from_prefixed_hex(s: &str) -> Result<Vec<u8>, hex::FromHexError> {
+    hex::decode(s.strip_prefix("0x").unwrap_or(s))
+}
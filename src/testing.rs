@@ -0,0 +1,114 @@
+//! Property testing strategies and round-trip properties for downstream use.
+//!
+//! Enabled with the `testing` feature. The strategies only generate valid
+//! values so they can be combined with the property functions below or with
+//! a caller's own serialization to fuzz it, e.g.
+//!
+//! ```ignore
+//! proptest! {
+//!     #[test]
+//!     fn public_keys(pk in public_key::<Bls12381G1Impl>()) {
+//!         serde_round_trip(&pk)?;
+//!     }
+//! }
+//! ```
+use crate::*;
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use serde::de::DeserializeOwned;
+
+/// Generate any of the signature schemes
+pub fn scheme() -> impl Strategy<Value = SignatureSchemes> {
+    prop_oneof![
+        Just(SignatureSchemes::Basic),
+        Just(SignatureSchemes::MessageAugmentation),
+        Just(SignatureSchemes::ProofOfPossession),
+    ]
+}
+
+/// Generate an arbitrary message up to 256 bytes long
+pub fn message() -> impl Strategy<Value = Vec<u8>> {
+    proptest::collection::vec(any::<u8>(), 0..=256)
+}
+
+/// Generate a valid non-zero secret key
+pub fn secret_key<C: BlsSignatureImpl + fmt::Debug>() -> impl Strategy<Value = SecretKey<C>> {
+    any::<[u8; 32]>()
+        .prop_map(SecretKey::<C>::from_hash)
+        .prop_filter("secret key is zero", |sk| !bool::from(sk.is_zero()))
+}
+
+/// Generate a valid public key
+pub fn public_key<C: BlsSignatureImpl + fmt::Debug>() -> impl Strategy<Value = PublicKey<C>> {
+    secret_key::<C>().prop_map(|sk| sk.public_key())
+}
+
+/// Generate a valid signature over an arbitrary message with any scheme
+pub fn signature<C: BlsSignatureImpl + fmt::Debug>() -> impl Strategy<Value = Signature<C>> {
+    (secret_key::<C>(), scheme(), message())
+        .prop_map(|(sk, scheme, msg)| sk.sign(scheme, &msg).expect("signing with a non-zero key"))
+}
+
+/// Generate a valid signcrypt ciphertext of an arbitrary message with any scheme
+pub fn sign_crypt_ciphertext<C: BlsSignatureImpl + fmt::Debug>(
+) -> impl Strategy<Value = SignCryptCiphertext<C>> {
+    (public_key::<C>(), scheme(), message(), any::<[u8; 32]>()).prop_map(
+        |(pk, scheme, msg, seed)| pk.sign_crypt_with_rng(scheme, msg, ChaCha20Rng::from_seed(seed)),
+    )
+}
+
+/// Deterministically create a `threshold` of `limit` committee from `seed`.
+///
+/// The same seed always yields the same group public key and secret key
+/// shares so integration tests across crates can share a canonical committee.
+///
+/// # Panics
+///
+/// If `threshold` and `limit` are not a valid sharing configuration
+pub fn deterministic_committee<C: BlsSignatureImpl>(
+    seed: [u8; 32],
+    threshold: usize,
+    limit: usize,
+) -> (PublicKey<C>, Vec<SecretKeyShare<C>>) {
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    let sk = SecretKey::<C>::random(&mut rng);
+    let shares = sk
+        .split_with_rng(threshold, limit, &mut rng)
+        .expect("invalid committee threshold or size");
+    (sk.public_key(), shares)
+}
+
+/// Check `value` survives conversion to and from the crate's byte format
+pub fn bytes_round_trip<T>(value: &T) -> Result<(), TestCaseError>
+where
+    T: PartialEq + fmt::Debug + for<'a> TryFrom<&'a [u8], Error = BlsError>,
+    for<'a> Vec<u8>: From<&'a T>,
+{
+    let bytes = Vec::<u8>::from(value);
+    let decoded = T::try_from(bytes.as_slice())
+        .map_err(|e| TestCaseError::fail(format!("failed to decode bytes: {}", e)))?;
+    prop_assert_eq!(value, &decoded);
+    Ok(())
+}
+
+/// Check `value` survives serialization with both a binary and a human
+/// readable serde format
+pub fn serde_round_trip<T>(value: &T) -> Result<(), TestCaseError>
+where
+    T: PartialEq + fmt::Debug + Serialize + DeserializeOwned,
+{
+    let bare = serde_bare::to_vec(value)
+        .map_err(|e| TestCaseError::fail(format!("failed to serialize: {}", e)))?;
+    let decoded: T = serde_bare::from_slice(&bare)
+        .map_err(|e| TestCaseError::fail(format!("failed to deserialize: {}", e)))?;
+    prop_assert_eq!(value, &decoded);
+
+    let json = serde_json::to_string(value)
+        .map_err(|e| TestCaseError::fail(format!("failed to serialize: {}", e)))?;
+    let decoded: T = serde_json::from_str(&json)
+        .map_err(|e| TestCaseError::fail(format!("failed to deserialize: {}", e)))?;
+    prop_assert_eq!(value, &decoded);
+    Ok(())
+}
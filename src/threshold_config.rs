@@ -0,0 +1,99 @@
+use crate::impls::inner_types::*;
+use crate::*;
+use vsss_rs::{IdentifierPrimeField, Share};
+
+/// The parameters of a `threshold`-of-`limit` secret sharing
+///
+/// Deserializing goes through [`ThresholdConfig::new`] so a stored
+/// configuration can't bypass its checks.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "ThresholdConfigRepr"))]
+pub struct ThresholdConfig {
+    /// The minimum number of shares required to combine
+    threshold: usize,
+    /// The total number of shares that were created
+    limit: usize,
+}
+
+/// The serialized form of [`ThresholdConfig`] before it's checked
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct ThresholdConfigRepr {
+    threshold: usize,
+    limit: usize,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ThresholdConfigRepr> for ThresholdConfig {
+    type Error = BlsError;
+
+    fn try_from(value: ThresholdConfigRepr) -> BlsResult<Self> {
+        Self::new(value.threshold, value.limit)
+    }
+}
+
+impl ThresholdConfig {
+    /// Create a new threshold configuration.
+    ///
+    /// The threshold must be at least 2 and cannot exceed the limit
+    pub fn new(threshold: usize, limit: usize) -> BlsResult<Self> {
+        if threshold < 2 {
+            return Err(BlsError::InvalidInputs(
+                "threshold cannot be less than 2".to_string(),
+            ));
+        }
+        if limit < threshold {
+            return Err(BlsError::InvalidInputs(
+                "limit cannot be less than threshold".to_string(),
+            ));
+        }
+        Ok(Self { threshold, limit })
+    }
+
+    /// The minimum number of shares required to combine
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// The total number of shares that were created
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Check that `shares` has at least `threshold` entries and
+    /// that every identifier is within `1..=limit`
+    pub(crate) fn check_shares<F, S>(&self, shares: &[S]) -> BlsResult<()>
+    where
+        F: PrimeField,
+        S: Share<Identifier = IdentifierPrimeField<F>>,
+    {
+        if shares.len() < self.threshold {
+            return Err(BlsError::InsufficientShares {
+                threshold: self.threshold,
+                received: shares.len(),
+            });
+        }
+        for share in shares {
+            let index = identifier_to_index(&share.identifier().0);
+            if !matches!(index, Some(i) if (1..=self.limit).contains(&i)) {
+                return Err(BlsError::InvalidInputs(format!(
+                    "share identifier is outside the range 1..={}",
+                    self.limit
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Convert a share identifier to its index if it fits in a `usize`
+fn identifier_to_index<F: PrimeField>(identifier: &F) -> Option<usize> {
+    let repr = identifier.to_repr();
+    let bytes = repr.as_ref();
+    let (low, high) = bytes.split_at(8);
+    if !bool::from(high.is_zero()) {
+        return None;
+    }
+    usize::try_from(u64::from_le_bytes(<[u8; 8]>::try_from(low).ok()?)).ok()
+}
@@ -1,12 +1,41 @@
 use crate::*;
 use subtle::CtOption;
 
+/// The point in the future a time lock ciphertext is locked to.
+///
+/// The beacon releases a signature over [`TimeLockTarget::to_bytes`] when the
+/// target is reached, which is the key that decrypts the ciphertext.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimeLockTarget {
+    /// A beacon round number, signed as its 8 byte big-endian encoding
+    Round(u64),
+    /// An arbitrary identifier signed as is, e.g. a timestamp or block hash
+    Identifier(Vec<u8>),
+}
+
+impl TimeLockTarget {
+    /// The message the beacon signs for this target
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Round(round) => round.to_be_bytes().to_vec(),
+            Self::Identifier(id) => id.clone(),
+        }
+    }
+}
+
 /// The ciphertext output from time lock encryption
-#[derive(Clone, Debug, Default, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TimeCryptCiphertext<C: BlsSignatureImpl> {
     /// The `u` component
-    #[serde(serialize_with = "traits::public_key::serialize::<C, _>")]
-    #[serde(deserialize_with = "traits::public_key::deserialize::<C, _>")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "traits::public_key::serialize::<C, _>",
+            deserialize_with = "traits::public_key::deserialize::<C, _>"
+        )
+    )]
     pub u: <C as Pairing>::PublicKey,
     /// The `v` component
     pub v: [u8; 32],
@@ -18,7 +47,7 @@ pub struct TimeCryptCiphertext<C: BlsSignatureImpl> {
 
 impl<C: BlsSignatureImpl> From<&TimeCryptCiphertext<C>> for Vec<u8> {
     fn from(value: &TimeCryptCiphertext<C>) -> Self {
-        serde_bare::to_vec(value).expect("failed to serialize time crypt ciphertext")
+        codec::to_vec(value)
     }
 }
 
@@ -26,11 +55,34 @@ impl<C: BlsSignatureImpl> TryFrom<&[u8]> for TimeCryptCiphertext<C> {
     type Error = BlsError;
 
     fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
-        let output = serde_bare::from_slice(value)?;
+        let output = codec::from_slice(value)?;
         Ok(output)
     }
 }
 
+impl<C: BlsSignatureImpl> codec::Encode for TimeCryptCiphertext<C> {
+    fn encode(&self, w: &mut codec::Writer) {
+        w.point(&self.u);
+        w.fixed(&self.v);
+        w.data(&self.w);
+        self.scheme.encode(w);
+    }
+}
+
+impl<C: BlsSignatureImpl> codec::Decode for TimeCryptCiphertext<C> {
+    fn decode(r: &mut codec::Reader<'_>) -> Result<Self, codec::DecodeError> {
+        let u = r.point()?;
+        let mut v = [0u8; 32];
+        v.copy_from_slice(r.fixed(32)?);
+        Ok(Self {
+            u,
+            v,
+            w: r.data()?.to_vec(),
+            scheme: SignatureSchemes::decode(r)?,
+        })
+    }
+}
+
 impl_from_derivatives_generic!(TimeCryptCiphertext);
 
 impl<C: BlsSignatureImpl> TimeCryptCiphertext<C> {
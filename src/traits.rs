@@ -7,6 +7,7 @@ mod hash_to_point;
 mod hash_to_scalar;
 mod pairings;
 mod pk_multi;
+#[cfg(feature = "serde")]
 mod serdes;
 mod sig_aug;
 mod sig_basic;
@@ -22,6 +23,7 @@ pub use hash_to_point::*;
 pub use hash_to_scalar::*;
 pub use pairings::*;
 pub use pk_multi::*;
+#[cfg(feature = "serde")]
 pub use serdes::*;
 pub use sig_aug::*;
 pub use sig_basic::*;
@@ -1,7 +1,9 @@
+#[cfg(feature = "serde")]
+use super::BlsSerde;
 use crate::impls::inner_types::*;
 use core::fmt::Display;
-use serde::de::DeserializeOwned;
-use serde::Serialize;
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Serialize};
 use subtle::ConditionallySelectable;
 use vsss_rs::*;
 
@@ -12,7 +14,7 @@ pub trait Pairing {
             Identifier = IdentifierPrimeField<<Self::PublicKey as Group>::Scalar>,
             Value = ValuePrimeField<<Self::PublicKey as Group>::Scalar>,
         > + core::fmt::Debug
-        + DeserializeOwned
+        + MaybeSerde
         + Default;
     /// The public key group
     type PublicKey: Group + GroupEncoding + Default + Display + ConditionallySelectable;
@@ -24,8 +26,7 @@ pub trait Pairing {
         + Display
         + core::fmt::Debug
         + ConditionallySelectable
-        + Serialize
-        + DeserializeOwned
+        + MaybeSerde
         + Default;
     /// The signature group
     type Signature: Group<Scalar = <Self::PublicKey as Group>::Scalar>
@@ -41,11 +42,38 @@ pub trait Pairing {
         + Display
         + core::fmt::Debug
         + ConditionallySelectable
-        + Serialize
-        + DeserializeOwned
+        + MaybeSerde
         + Default;
     /// The target group from a pairing computation
     type PairingResult: Group + GroupEncoding + Default + Display + ConditionallySelectable;
     /// Compute the pairing based on supplied points
     fn pairing(points: &[(Self::Signature, Self::PublicKey)]) -> Self::PairingResult;
 }
+
+/// [`Serialize`] and [`DeserializeOwned`] when the `serde` feature is enabled
+#[cfg(feature = "serde")]
+pub trait MaybeSerde: Serialize + DeserializeOwned {}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize + DeserializeOwned> MaybeSerde for T {}
+
+/// `Serialize` and `DeserializeOwned` when the `serde` feature is enabled
+#[cfg(not(feature = "serde"))]
+pub trait MaybeSerde {}
+
+#[cfg(not(feature = "serde"))]
+impl<T> MaybeSerde for T {}
+
+/// [`BlsSerde`] when the `serde` feature is enabled
+#[cfg(feature = "serde")]
+pub trait MaybeBlsSerde: BlsSerde {}
+
+#[cfg(feature = "serde")]
+impl<T: BlsSerde> MaybeBlsSerde for T {}
+
+/// `BlsSerde` when the `serde` feature is enabled
+#[cfg(not(feature = "serde"))]
+pub trait MaybeBlsSerde: Pairing {}
+
+#[cfg(not(feature = "serde"))]
+impl<T: Pairing> MaybeBlsSerde for T {}
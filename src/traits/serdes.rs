@@ -135,4 +135,161 @@ pub(crate) mod scalar {
     ) -> Result<<B::PublicKey as Group>::Scalar, D::Error> {
         B::deserialize_scalar(d)
     }
+
+    /// Deserialize a scalar that must not be zero such as a secret key
+    pub fn deserialize_non_zero<'de, B: BlsSerde, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<<B::PublicKey as Group>::Scalar, D::Error> {
+        let scalar = B::deserialize_scalar(d)?;
+        if scalar.is_zero().into() {
+            return Err(serde::de::Error::custom(crate::BlsError::InvalidSecretKey));
+        }
+        Ok(scalar)
+    }
+}
+
+/// The stable serde layout of secret key, public key and decryption shares.
+///
+/// A share is a struct with two fields in this order: `identifier`, the
+/// scalar representation of the share's x-coordinate, and `value`, either a
+/// scalar representation or a compressed point. Each is a lowercase hex
+/// string for human readable formats and a byte string otherwise, so bincode
+/// writes a little endian `u64` length followed by the bytes.
+///
+/// This is the layout vsss-rs 5 derives. It is written out here so upgrading
+/// the dependency can't silently change the wire format.
+pub(crate) mod share {
+    use super::*;
+    use serde::{
+        de::{self, SeqAccess, Visitor},
+        Deserialize, Serialize,
+    };
+    use std::{borrow::Cow, fmt};
+    use vsss_rs::{DefaultShare, IdentifierPrimeField, ValueGroup};
+
+    type ScalarShare<F> = DefaultShare<IdentifierPrimeField<F>, IdentifierPrimeField<F>>;
+    type PointShare<G> = DefaultShare<IdentifierPrimeField<<G as Group>::Scalar>, ValueGroup<G>>;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "DefaultShare")]
+    struct Layout<'a> {
+        #[serde(with = "share_bytes")]
+        identifier: Cow<'a, [u8]>,
+        #[serde(with = "share_bytes")]
+        value: Cow<'a, [u8]>,
+    }
+
+    mod share_bytes {
+        use super::*;
+
+        pub fn serialize<B: AsRef<[u8]>, S: Serializer>(
+            bytes: &B,
+            s: S,
+        ) -> Result<S::Ok, S::Error> {
+            if s.is_human_readable() {
+                s.serialize_str(&hex::encode(bytes))
+            } else {
+                s.serialize_bytes(bytes.as_ref())
+            }
+        }
+
+        pub fn deserialize<'de, 'a, D: Deserializer<'de>>(d: D) -> Result<Cow<'a, [u8]>, D::Error> {
+            struct BytesVisitor;
+
+            impl<'de> Visitor<'de> for BytesVisitor {
+                type Value = Vec<u8>;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "a hex string or byte sequence")
+                }
+
+                fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                    hex::decode(v).map_err(E::custom)
+                }
+
+                fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                    Ok(v.to_vec())
+                }
+
+                fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                    let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                    while let Some(b) = seq.next_element()? {
+                        bytes.push(b);
+                    }
+                    Ok(bytes)
+                }
+            }
+
+            let bytes = if d.is_human_readable() {
+                d.deserialize_str(BytesVisitor)?
+            } else {
+                d.deserialize_bytes(BytesVisitor)?
+            };
+            Ok(Cow::Owned(bytes))
+        }
+    }
+
+    fn scalar_from_bytes<F: PrimeField, E: de::Error>(bytes: &[u8]) -> Result<F, E> {
+        let mut repr = F::Repr::default();
+        if repr.as_ref().len() != bytes.len() {
+            return Err(E::invalid_length(bytes.len(), &"a scalar representation"));
+        }
+        repr.as_mut().copy_from_slice(bytes);
+        Option::from(F::from_repr(repr)).ok_or_else(|| E::custom("invalid prime field element"))
+    }
+
+    fn point_from_bytes<G: GroupEncoding, E: de::Error>(bytes: &[u8]) -> Result<G, E> {
+        let mut repr = G::Repr::default();
+        if repr.as_ref().len() != bytes.len() {
+            return Err(E::invalid_length(bytes.len(), &"a compressed point"));
+        }
+        repr.as_mut().copy_from_slice(bytes);
+        Option::from(G::from_bytes(&repr)).ok_or_else(|| E::custom("invalid group element"))
+    }
+
+    /// Serialize a share whose value is a scalar
+    pub fn serialize_scalar<F: PrimeField, S: Serializer>(
+        share: &ScalarShare<F>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        Layout {
+            identifier: Cow::Borrowed(share.identifier.0.to_repr().as_ref()),
+            value: Cow::Borrowed(share.value.0.to_repr().as_ref()),
+        }
+        .serialize(s)
+    }
+
+    /// Deserialize a share whose value is a scalar
+    pub fn deserialize_scalar<'de, F: PrimeField, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<ScalarShare<F>, D::Error> {
+        let layout = Layout::deserialize(d)?;
+        Ok(DefaultShare {
+            identifier: IdentifierPrimeField(scalar_from_bytes(&layout.identifier)?),
+            value: IdentifierPrimeField(scalar_from_bytes(&layout.value)?),
+        })
+    }
+
+    /// Serialize a share whose value is a point
+    pub fn serialize_point<G: Group + GroupEncoding + Default, S: Serializer>(
+        share: &PointShare<G>,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        Layout {
+            identifier: Cow::Borrowed(share.identifier.0.to_repr().as_ref()),
+            value: Cow::Borrowed(share.value.0.to_bytes().as_ref()),
+        }
+        .serialize(s)
+    }
+
+    /// Deserialize a share whose value is a point
+    pub fn deserialize_point<'de, G: Group + GroupEncoding + Default, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<PointShare<G>, D::Error> {
+        let layout = Layout::deserialize(d)?;
+        Ok(DefaultShare {
+            identifier: IdentifierPrimeField(scalar_from_bytes(&layout.identifier)?),
+            value: ValueGroup(point_from_bytes(&layout.value)?),
+        })
+    }
 }
@@ -6,7 +6,7 @@ use vsss_rs::*;
 pub trait BlsSignatureCore:
     Pairing
     + HashToPoint<Output = Self::Signature>
-    + BlsSerde
+    + MaybeBlsSerde
     + BlsSignatureProof
     + BlsSignCrypt
     + BlsTimeCrypt
@@ -124,18 +124,31 @@ pub trait BlsSignatureCore:
         dst: C,
     ) -> BlsResult<()> {
         if sig.is_identity().into() {
-            return Err(BlsError::InvalidInputs(
-                "signature is the identity point".to_string(),
-            ));
+            return Err(BlsError::InvalidSignature);
         }
         if pk.is_identity().into() {
-            return Err(BlsError::InvalidInputs(
-                "public key is the identity point".to_string(),
-            ));
+            return Err(BlsError::InvalidPublicKey);
         }
         let a = Self::hash_to_point::<B, C>(msg, dst);
+        Self::core_verify_point(pk, sig, a)
+    }
+
+    /// Verify a signature against an already computed message hash point
+    fn core_verify_point(
+        pk: Self::PublicKey,
+        sig: Self::Signature,
+        hash: Self::Signature,
+    ) -> BlsResult<()> {
+        // The identity signature and public key are rejected outright since
+        // they can satisfy the pairing equation without knowledge of the key
+        if sig.is_identity().into() {
+            return Err(BlsError::InvalidSignature);
+        }
+        if pk.is_identity().into() {
+            return Err(BlsError::InvalidPublicKey);
+        }
         let generator = -Self::PublicKey::generator();
-        if Self::pairing(&[(a, pk), (sig, generator)])
+        if Self::pairing(&[(hash, pk), (sig, generator)])
             .is_identity()
             .into()
         {
@@ -169,6 +182,9 @@ pub trait BlsSignatureCore:
             debug_assert_eq!(a.is_identity().unwrap_u8(), 0u8);
             pairs.push((a, pk));
         }
+        if pairs.is_empty() {
+            return Err(BlsError::EmptyAggregate);
+        }
         pairs.push((sig, -<Self::PublicKey as Group>::generator()));
         if Self::pairing(pairs.as_slice()).is_identity().into() {
             Ok(())
@@ -3,11 +3,12 @@ use crate::helpers::*;
 use crate::impls::inner_types::*;
 use crate::{BlsError, BlsResult};
 use rand::Rng;
+use rand_core::{CryptoRng, RngCore};
 use sha3::{
     digest::{ExtendableOutput, Update, XofReader},
     Shake128,
 };
-use subtle::{Choice, ConditionallySelectable, CtOption};
+use subtle::{Choice, CtOption};
 use vsss_rs::*;
 
 /// The methods for implementing SignCryption
@@ -36,28 +37,58 @@ pub trait BlsSignCrypt:
         message: B,
         dst: &[u8],
     ) -> (Self::PublicKey, Vec<u8>, Self::Signature) {
-        const SALT: &[u8] = b"SIGNCRYPT_BLS12381_XOF:HKDF-SHA2-256_";
-        let message = message.as_ref();
-
-        // r ← Zq
-        let r = Self::hash_to_scalar(get_crypto_rng().gen::<[u8; 32]>(), SALT);
-        debug_assert_eq!(r.is_zero().unwrap_u8(), 0u8);
-        // U = P^r
-        let u = Self::PublicKey::generator() * r;
-        debug_assert_eq!(u.is_identity().unwrap_u8(), 0u8);
-        // V = HℓX(R) ⊕ M
-        let overhead = uint_zigzag::Uint::from(message.len());
-        let mut overhead_bytes = overhead.to_vec();
-        overhead_bytes.extend_from_slice(message);
-        // Always use at least 32 bytes
-        while overhead_bytes.len() < 32 {
-            overhead_bytes.push(0u8);
+        Self::seal_with_rng(pk, message, dst, get_crypto_rng())
+    }
+
+    /// Create a new ciphertext drawing the ephemeral `r` from a specified RNG
+    fn seal_with_rng<B: AsRef<[u8]>>(
+        pk: Self::PublicKey,
+        message: B,
+        dst: &[u8],
+        rng: impl RngCore + CryptoRng,
+    ) -> (Self::PublicKey, Vec<u8>, Self::Signature) {
+        Self::seal_padded_with_rng(pk, message, dst, 1, rng)
+    }
+
+    /// Create a new ciphertext whose encrypted message `V` is zero padded to a
+    /// multiple of `block_size` bytes.
+    ///
+    /// The length prefix in front of the message tells [`BlsSignCrypt::decrypt`]
+    /// where the padding starts, and `W` authenticates all of `V` so the padding
+    /// can't be changed without the ciphertext becoming invalid.
+    /// A `block_size` of zero is treated as one.
+    fn seal_padded_with_rng<B: AsRef<[u8]>>(
+        pk: Self::PublicKey,
+        message: B,
+        dst: &[u8],
+        block_size: usize,
+        rng: impl RngCore + CryptoRng,
+    ) -> (Self::PublicKey, Vec<u8>, Self::Signature) {
+        seal_inner::<Self>(|r| pk * r, message.as_ref(), dst, block_size, rng)
+    }
+
+    /// Create a ciphertext for each message to the same recipient
+    ///
+    /// Every message gets its own fresh ephemeral `r`, none are reused. Only
+    /// the work that depends on `pk` alone is shared: a table of multiples of
+    /// `pk` is built once so each `K^r` takes additions instead of a full
+    /// scalar multiplication
+    fn seal_batch_with_rng<B: AsRef<[u8]>>(
+        pk: Self::PublicKey,
+        messages: &[B],
+        dst: &[u8],
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Vec<(Self::PublicKey, Vec<u8>, Self::Signature)> {
+        if messages.is_empty() {
+            return Vec::new();
         }
-        let v = Self::compute_v(pk * r, overhead_bytes.as_slice());
-        // W = HG(U′ || V)^r
-        let w = Self::compute_w(u, v.as_slice(), dst) * r;
-        debug_assert_eq!(w.is_identity().unwrap_u8(), 0u8);
-        (u, v, w)
+        let table = FixedBaseTable::new(pk);
+        messages
+            .iter()
+            .map(|message| {
+                seal_inner::<Self>(|r| table.mul(&r), message.as_ref(), dst, 1, &mut rng)
+            })
+            .collect()
     }
 
     /// Check if the ciphertext is valid
@@ -82,6 +113,10 @@ pub trait BlsSignCrypt:
     /// 1. Verify the ciphertext is valid
     /// 2. G = U^sk
     /// 4. m = HℓX(G) ⊕ V
+    ///
+    /// Every step runs whether or not the ciphertext is valid so an
+    /// authentication failure takes as long as a success. The plaintext of
+    /// an invalid ciphertext is masked by the returned [`CtOption`]
     fn unseal(
         u: Self::PublicKey,
         v: &[u8],
@@ -90,11 +125,9 @@ pub trait BlsSignCrypt:
         dst: &[u8],
     ) -> CtOption<Vec<u8>> {
         let valid = Self::valid(u, v, w, dst);
-        let ua = u * ConditionallySelectable::conditional_select(
-            &<Self::PublicKey as Group>::Scalar::ZERO,
-            sk,
-            valid,
-        );
+        // Multiplying by a selected zero scalar or hashing the identity
+        // is measurably faster so always use the real key
+        let ua = u * sk;
         Self::decrypt(v, ua, valid)
     }
 
@@ -119,20 +152,25 @@ pub trait BlsSignCrypt:
     }
 
     /// Decrypt a ciphertext
+    ///
+    /// Authentication failures take the same path as successes: the length
+    /// prefix is always parsed and the same number of bytes copied so only
+    /// the returned [`Choice`] differs
     fn decrypt(v: &[u8], ua: Self::PublicKey, valid: Choice) -> CtOption<Vec<u8>> {
-        let plaintext = Self::compute_v(ua, v);
-        if let Some(overhead) = uint_zigzag::Uint::peek(plaintext.as_slice()) {
-            // If peek succeeds then try_from will also, so unwrap is okay.
-            // peek returns the amount actually used whereas try_from does not
-            // thus both are used.
-            let len = uint_zigzag::Uint::try_from(&plaintext[..overhead])
-                .unwrap()
-                .0 as usize;
-            if len <= plaintext.len() - overhead {
-                return CtOption::new(plaintext[overhead..overhead + len].to_vec(), valid);
-            }
-        }
-        CtOption::new(v.to_vec(), 0u8.into())
+        let mut plaintext = Self::compute_v(ua, v);
+        // peek returns the amount actually used whereas try_from does not
+        // thus both are used. If peek succeeds then try_from will also.
+        let overhead = uint_zigzag::Uint::peek(plaintext.as_slice()).unwrap_or(0);
+        let len = uint_zigzag::Uint::try_from(&plaintext[..overhead])
+            .map(|l| l.0 as usize)
+            .unwrap_or(usize::MAX);
+        let well_formed =
+            Choice::from(u8::from(overhead != 0 && len <= plaintext.len() - overhead));
+        let len = len.min(plaintext.len() - overhead);
+        // Shift the whole remainder rather than just the message
+        plaintext.copy_within(overhead.., 0);
+        plaintext.truncate(len);
+        CtOption::new(plaintext, valid & well_formed)
     }
 
     /// Compute the `V` value
@@ -144,7 +182,7 @@ pub trait BlsSignCrypt:
 
         let mut v = vec![0u8; r.len()];
         reader.read(&mut v);
-        debug_assert!(!v.iter().all(|x| *x == 0));
+        debug_assert!(v.is_empty() || !v.iter().all(|x| *x == 0));
         // V = HℓX(R) ⊕ M
         byte_xor(r, &v)
     }
@@ -206,3 +244,38 @@ pub trait BlsSignCrypt:
             & Self::pairing(&[(hash, share), (w, pk)]).is_identity()
     }
 }
+
+/// Seal `message` computing `K^r` with `key_mul` so batches can share work
+/// that depends only on the recipient's key
+fn seal_inner<S: BlsSignCrypt + ?Sized>(
+    key_mul: impl Fn(<S::PublicKey as Group>::Scalar) -> S::PublicKey,
+    message: &[u8],
+    dst: &[u8],
+    block_size: usize,
+    mut rng: impl RngCore + CryptoRng,
+) -> (S::PublicKey, Vec<u8>, S::Signature) {
+    const SALT: &[u8] = b"SIGNCRYPT_BLS12381_XOF:HKDF-SHA2-256_";
+
+    // r ← Zq
+    let r = S::hash_to_scalar(rng.gen::<[u8; 32]>(), SALT);
+    debug_assert_eq!(r.is_zero().unwrap_u8(), 0u8);
+    // U = P^r
+    let u = S::PublicKey::generator() * r;
+    debug_assert_eq!(u.is_identity().unwrap_u8(), 0u8);
+    // V = HℓX(R) ⊕ M
+    let overhead = uint_zigzag::Uint::from(message.len());
+    let mut overhead_bytes = overhead.to_vec();
+    overhead_bytes.extend_from_slice(message);
+    // Always use at least 32 bytes
+    while overhead_bytes.len() < 32 {
+        overhead_bytes.push(0u8);
+    }
+    let block_size = block_size.max(1);
+    let padded_len = overhead_bytes.len().div_ceil(block_size) * block_size;
+    overhead_bytes.resize(padded_len, 0u8);
+    let v = S::compute_v(key_mul(r), overhead_bytes.as_slice());
+    // W = HG(U′ || V)^r
+    let w = S::compute_w(u, v.as_slice(), dst) * r;
+    debug_assert_eq!(w.is_identity().unwrap_u8(), 0u8);
+    (u, v, w)
+}
@@ -41,7 +41,7 @@ pub trait BlsTimeCrypt:
             .as_ref()
             .iter()
             .copied()
-            .chain(msg_dst.as_slice().iter().copied())
+            .chain(msg_dst.iter().copied())
             .collect();
         let r = Self::hash_to_scalar(r_input.as_slice(), SALT);
         debug_assert_eq!(r.is_zero().unwrap_u8(), 0u8);
@@ -105,7 +105,7 @@ pub trait BlsTimeCrypt:
         let r_input: Vec<u8> = alpha
             .iter()
             .copied()
-            .chain(msg_dst.as_slice().iter().copied())
+            .chain(msg_dst.iter().copied())
             .collect();
         let r = Self::hash_to_scalar(r_input.as_slice(), SALT);
         debug_assert_eq!(r.is_zero().unwrap_u8(), 0u8);
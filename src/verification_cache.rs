@@ -0,0 +1,158 @@
+use crate::*;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::marker::PhantomData;
+use std::sync::Mutex;
+
+/// A bounded cache of prior signature verification outcomes.
+///
+/// Entries are keyed by a hash of the public key, signature and message so
+/// verifying the same triple again returns the stored result instead of
+/// computing the pairing. When full the least recently used entry is evicted.
+/// The cache is safe to share between threads.
+pub struct VerificationCache<C: BlsSignatureImpl> {
+    capacity: usize,
+    inner: Mutex<LruState>,
+    _marker: PhantomData<fn() -> C>,
+}
+
+#[derive(Default)]
+struct LruState {
+    tick: u64,
+    entries: HashMap<[u8; 32], (BlsResult<()>, u64)>,
+    recency: BTreeMap<u64, [u8; 32]>,
+}
+
+impl<C: BlsSignatureImpl> fmt::Debug for VerificationCache<C> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("VerificationCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<C: BlsSignatureImpl> VerificationCache<C> {
+    /// Create a cache that holds at most `capacity` verification outcomes
+    pub fn new(capacity: usize) -> BlsResult<Self> {
+        if capacity == 0 {
+            return Err(BlsError::InvalidInputs(
+                "capacity cannot be zero".to_string(),
+            ));
+        }
+        Ok(Self {
+            capacity,
+            inner: Mutex::new(LruState::default()),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Verify `sig` over `msg` with `pk`, returning the cached outcome if
+    /// this triple has been verified before
+    pub fn verify<B: AsRef<[u8]>>(
+        &self,
+        pk: &PublicKey<C>,
+        sig: &Signature<C>,
+        msg: B,
+    ) -> BlsResult<()> {
+        let key = Self::key(pk, sig, msg.as_ref());
+        if let Some(result) = self.lock().get(&key) {
+            return result;
+        }
+        let result = sig.verify(pk, msg);
+        self.lock().insert(key, result.clone(), self.capacity);
+        result
+    }
+
+    /// The maximum number of outcomes held by this cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of outcomes currently held by this cache
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// True if the cache holds no outcomes
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all cached outcomes
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.recency.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruState> {
+        // The state is always left consistent so a poisoned lock is still usable
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn key(pk: &PublicKey<C>, sig: &Signature<C>, msg: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(Vec::from(pk));
+        hasher.update(Vec::from(sig));
+        hasher.update((msg.len() as u64).to_be_bytes());
+        hasher.update(msg);
+        hasher.finalize().into()
+    }
+}
+
+impl LruState {
+    fn get(&mut self, key: &[u8; 32]) -> Option<BlsResult<()>> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (result, last_used) = self.entries.get_mut(key)?;
+        self.recency.remove(last_used);
+        self.recency.insert(tick, *key);
+        *last_used = tick;
+        Some(result.clone())
+    }
+
+    fn insert(&mut self, key: [u8; 32], result: BlsResult<()>, capacity: usize) {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some((_, last_used)) = self.entries.insert(key, (result, tick)) {
+            self.recency.remove(&last_used);
+        }
+        self.recency.insert(tick, key);
+        while self.entries.len() > capacity {
+            match self.recency.pop_first() {
+                Some((_, oldest)) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let sk = SecretKey::<Bls12381G2Impl>::from_hash(b"evicts_least_recently_used");
+        let pk = sk.public_key();
+        let cache = VerificationCache::new(2).unwrap();
+        let msgs: [&[u8]; 3] = [b"one", b"two", b"three"];
+        let sigs = msgs.map(|m| sk.sign(SignatureSchemes::Basic, m).unwrap());
+
+        cache.verify(&pk, &sigs[0], msgs[0]).unwrap();
+        cache.verify(&pk, &sigs[1], msgs[1]).unwrap();
+        // touch the first entry so the second becomes the oldest
+        cache.verify(&pk, &sigs[0], msgs[0]).unwrap();
+        cache.verify(&pk, &sigs[2], msgs[2]).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        let key0 = VerificationCache::key(&pk, &sigs[0], msgs[0]);
+        let key1 = VerificationCache::key(&pk, &sigs[1], msgs[1]);
+        let state = cache.lock();
+        assert!(state.entries.contains_key(&key0));
+        assert!(!state.entries.contains_key(&key1));
+    }
+}
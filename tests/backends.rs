@@ -0,0 +1,150 @@
+//! Fixed vectors that every backend must reproduce.
+//!
+//! Run with each of the backend feature sets, e.g.
+//! `cargo test --test backends`,
+//! `cargo test --test backends --features portable` and
+//! `cargo test --test backends --no-default-features --features rust`,
+//! to confirm the assembly, portable and pure rust arithmetic agree.
+use blsful::*;
+use rstest::*;
+
+const SEED: &[u8] = b"backend_vectors";
+
+const G1_PUBLIC_KEY: &str =
+    "879120fd698e961ea71934b641ac53b9364e8e26ec9b32d1133db96a814f3f95d97f8dbc36c4b381accbd3d6eb644ddf149c3943934c3c25286cfea5db3fa44b9b6bc3a3550ae51117062cba8480b49183e92b35ce7e7513280f8caf6b102211";
+
+const G1_SIGNATURES: &[(SignatureSchemes, &[u8], &str)] = &[
+    (
+        SignatureSchemes::Basic,
+        b"",
+        "0082470059ddaed93930eadf5566bd170fa889060770b1fe9e40f5dc48c1afa80307ff7f7c7f8ec0958d3e0b39c65f3053",
+    ),
+    (
+        SignatureSchemes::Basic,
+        b"backend",
+        "008de40114eb211a6d094525c6b668cc65126925c0b276948e362bae29e455757601bd15097b555ec86be0be8f8f16402b",
+    ),
+    (
+        SignatureSchemes::Basic,
+        b"the quick brown fox jumps over the lazy dog",
+        "00a0a32eb017923063dcd402c61766a325f65d5d7b9c4e63e5b7cf67c3c49e1aad8f56417c930fedff56a6ae0ff1a38f2b",
+    ),
+    (
+        SignatureSchemes::MessageAugmentation,
+        b"",
+        "01834d53cb0e458ea7faeb4d8443d799a6bc0fd13df8aca8019d40ead81ab7e05aabe37d11c917e6aad2221cf0b24a7fc3",
+    ),
+    (
+        SignatureSchemes::MessageAugmentation,
+        b"backend",
+        "01855d287c54de137c2c4566ba6490b652a3379dbd754e0d1959758728dc8bb718fa9e85e1184878b53987aa791ffbd843",
+    ),
+    (
+        SignatureSchemes::MessageAugmentation,
+        b"the quick brown fox jumps over the lazy dog",
+        "0195128cf5fac44ce0ea5f95c5880701c785b4d7107839ef532572f10130107b64d1446dd55b853d38364b63cb780b9d9a",
+    ),
+    (
+        SignatureSchemes::ProofOfPossession,
+        b"",
+        "0297bc51fff1ac9081ccd20facc59087cfcceecc40075d12f63f78fc7b03e01c2dafc09a1f22bd835473eb54aa3655d02f",
+    ),
+    (
+        SignatureSchemes::ProofOfPossession,
+        b"backend",
+        "02a89cb71e7220bee0d9019b92453a2d0a2df95b9ab647c22200ec7c0e6ab2a606831a3f1100558bddb3ba065c011918f9",
+    ),
+    (
+        SignatureSchemes::ProofOfPossession,
+        b"the quick brown fox jumps over the lazy dog",
+        "028080b23ad3922bf57291939ec26385074d1e225d56758092d0277c7c5980005dce5c409d4b9dab43cce5f605211f96f4",
+    ),
+];
+
+const G2_PUBLIC_KEY: &str =
+    "b16e7934d45a0885cdd202724829f802bf61e2e8eb1ede020558d373eeadaad62b85b4df660d2a479076e242183e4aff";
+
+const G2_SIGNATURES: &[(SignatureSchemes, &[u8], &str)] = &[
+    (
+        SignatureSchemes::Basic,
+        b"",
+        "00a9dde68226310039b86b4b1d808dfe95f6fd85893ba6fb83329ceb732988034c9e3442adc8c504d28cf9fe0354602cac04fc66831760f85a16d5490d5b87f2179e1dff4525b6e46569b2fce006d28539b6caaed5b0860eb9d0fb7213c75bbcd7",
+    ),
+    (
+        SignatureSchemes::Basic,
+        b"backend",
+        "00b4695a8f22cfb86f37b6e86befb597a4699fb509823bec86eb1541a6c1762f0c4d50bd3ed8dec94937f3c40bbbd5f76b00f2d43e8ab6a0133186c511f357ef97a8b02bccc7181791241b6829333cfb0e811dd42eeb2d29bd2631c55185eee1e0",
+    ),
+    (
+        SignatureSchemes::Basic,
+        b"the quick brown fox jumps over the lazy dog",
+        "00814bdb892eecd6b411560edb886f3e8ec0b11900049820bbf0a2270575c7c4a8f30f8d584f34bc43ff54c3ac51265763170f94d51712fa5c6bb0be7e64aa7d4c66a2c43c39d35788a5d843e71d3ab29d378c438edc9240a07a6b638c2f50b0da",
+    ),
+    (
+        SignatureSchemes::MessageAugmentation,
+        b"",
+        "01aae97fd49b8fd2221f40f987945554b3719b59a4651fea2167830aae9902f397b12c6fc5eb06527db753bbc0e65400af1358127253cecc6ae0501b83430540ab878cc3260810a8d8585ecfe4e12624530439a48f9f72fa696fb2d3191715eac0",
+    ),
+    (
+        SignatureSchemes::MessageAugmentation,
+        b"backend",
+        "0190e4eee4121063d48a645044d4a1d8fe5010dba47b48205912ddcb30b4f1e16a596a9bb1925aa887fd903da630b7383718abed50376fb52db2f6354b127402190c146cd472c5de7c1f1000e4700b70ba3644d6d214eb53a29128306b22215bdc",
+    ),
+    (
+        SignatureSchemes::MessageAugmentation,
+        b"the quick brown fox jumps over the lazy dog",
+        "01af60a5e554445ea8444b6510560090eb475820e4199cb4d4dee3648a5115f06108913bf88186adf0cb993484dea748a80ea6c0528865b5f39bbe89d2213cb5e9c7e79dba288533d74d88ff6cd67a8cf213b9663d71c3941da23aee761bf98326",
+    ),
+    (
+        SignatureSchemes::ProofOfPossession,
+        b"",
+        "02b4d88bf2f7bf6ba6514e40b0312dc627075310d8c10a7b44f72f8a3caef88670ae5ab589f5565931a00f6ad5fa5c266d0634bc6fe0949680df4da91f686c3c0df23b7fd0e69c85d913cc6cf844bfe7ca61a3abf05d26c2c0c1dc991b640221ac",
+    ),
+    (
+        SignatureSchemes::ProofOfPossession,
+        b"backend",
+        "0291bce9c1d40dd5eec0fe9f8bdebab10854d21237fa7eebb7907b86e97795771ca21cd60aa558017650470fd2258eb02210e94efb5d1fcb734821cf27be8473d766d5830f897383732c0a6775acbdb884caa5adb9d8540796fbd39d7bd5c8fec1",
+    ),
+    (
+        SignatureSchemes::ProofOfPossession,
+        b"the quick brown fox jumps over the lazy dog",
+        "0281d7ed924a7b4b9fa25e97a3b578f6dd77fda1228f086e211aee5080ff553eea6c7c47d8b537652caf21c3b565f6044100d53ed738d281f83dff9337907937823e8d6b84adf3ff569d9249c291b60a8fb9f2a0270d793d1bfb55f652dbe66a7a",
+    ),
+];
+
+#[rstest]
+#[case::g1(Bls12381G1Impl, G1_PUBLIC_KEY, G1_SIGNATURES)]
+#[case::g2(Bls12381G2Impl, G2_PUBLIC_KEY, G2_SIGNATURES)]
+fn backend_vectors<C: BlsSignatureImpl>(
+    #[case] _c: C,
+    #[case] expected_pk: &str,
+    #[case] vectors: &[(SignatureSchemes, &[u8], &str)],
+) {
+    let sk = SecretKey::<C>::from_hash(SEED);
+    let pk = sk.public_key();
+    assert_eq!(hex::encode(Vec::from(&pk)), expected_pk);
+
+    for (scheme, msg, expected_sig) in vectors {
+        let sig = sk.sign(*scheme, msg).unwrap();
+        assert_eq!(hex::encode(Vec::from(&sig)), *expected_sig);
+
+        let sig = Signature::<C>::try_from(hex::decode(expected_sig).unwrap()).unwrap();
+        assert!(sig.verify(&pk, msg).is_ok());
+        assert!(sig.verify(&pk, b"not the message").is_err());
+    }
+
+    let basic = vectors
+        .iter()
+        .filter(|(scheme, _, _)| *scheme == SignatureSchemes::Basic)
+        .map(|(_, msg, sig)| {
+            (
+                *msg,
+                Signature::<C>::try_from(hex::decode(sig).unwrap()).unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let sigs = basic.iter().map(|(_, s)| *s).collect::<Vec<_>>();
+    let asig = AggregateSignature::from_signatures(sigs).unwrap();
+    let data = basic.iter().map(|(m, _)| (pk, *m)).collect::<Vec<_>>();
+    assert!(asig.verify(&data).is_ok());
+}
@@ -0,0 +1,7 @@
+/// Public keys and signatures from different curves must not be mixable
+/// and signature shares can't stand in for signcrypt decryption shares
+#[test]
+fn cross_curve_usage_does_not_compile() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/*.rs");
+}
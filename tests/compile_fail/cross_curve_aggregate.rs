@@ -0,0 +1,9 @@
+use blsful::*;
+
+fn main() {
+    let sk_g1 = SecretKey::<Bls12381G1Impl>::new();
+    let sk_g2 = SecretKey::<Bls12381G2Impl>::new();
+    let sig_g1 = sk_g1.sign(SignatureSchemes::Basic, b"msg").unwrap();
+    let sig_g2 = sk_g2.sign(SignatureSchemes::Basic, b"msg").unwrap();
+    let _ = AggregateSignature::from_signatures([sig_g1, sig_g2]);
+}
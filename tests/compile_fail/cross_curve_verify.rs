@@ -0,0 +1,8 @@
+use blsful::*;
+
+fn main() {
+    let sk_g1 = SecretKey::<Bls12381G1Impl>::new();
+    let sk_g2 = SecretKey::<Bls12381G2Impl>::new();
+    let sig = sk_g1.sign(SignatureSchemes::Basic, b"msg").unwrap();
+    let _ = sig.verify(&sk_g2.public_key(), b"msg");
+}
@@ -0,0 +1,12 @@
+use blsful::*;
+
+fn main() {
+    let sk = SecretKey::<Bls12381G1Impl>::new();
+    let shares = sk.split(2, 3).unwrap();
+    let ciphertext = sk.public_key().sign_crypt(SignatureSchemes::Basic, b"msg");
+    let sig_shares = shares
+        .iter()
+        .map(|s| s.sign(SignatureSchemes::Basic, b"msg").unwrap())
+        .collect::<Vec<_>>();
+    let _ = ciphertext.decrypt_with_shares(&sig_shares);
+}
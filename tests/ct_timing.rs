@@ -0,0 +1,74 @@
+//! Statistical timing checks run with `cargo test --release --features ct-test`
+#![cfg(feature = "ct-test")]
+
+mod utils;
+use blsful::*;
+use rand_core::SeedableRng;
+use rstest::*;
+use std::time::Instant;
+use utils::*;
+
+const SAMPLES: usize = 400;
+/// The Welch t-statistic above which the timings are considered to differ,
+/// the same threshold dudect uses to report a leak
+const T_THRESHOLD: f64 = 4.5;
+
+/// Welch's t-statistic of two timing samples after dropping the slowest
+/// 10% of the combined measurements, which are mostly scheduler noise
+fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+    let mut all = a.iter().chain(b).copied().collect::<Vec<_>>();
+    all.sort_by(f64::total_cmp);
+    let cutoff = all[all.len() * 9 / 10];
+    let stats = |s: &[f64]| {
+        let s = s
+            .iter()
+            .copied()
+            .filter(|x| *x <= cutoff)
+            .collect::<Vec<_>>();
+        let n = s.len() as f64;
+        let mean = s.iter().sum::<f64>() / n;
+        let var = s.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (mean, var, n)
+    };
+    let (mean_a, var_a, n_a) = stats(a);
+    let (mean_b, var_b, n_b) = stats(b);
+    (mean_a - mean_b) / (var_a / n_a + var_b / n_b).sqrt()
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_decrypt_failure_timing<C: BlsSignatureImpl + Clone>(#[case] _c: C) {
+    let sk = SecretKey::<C>::from_hash(b"ct-test");
+    let valid = sk.public_key().sign_crypt_with_rng(
+        SignatureSchemes::ProofOfPossession,
+        TEST_MSG,
+        MockRng::from_seed([1u8; 16]),
+    );
+    let mut tampered = valid.clone();
+    tampered.v[3] ^= 1;
+    assert_eq!(valid.decrypt(&sk).is_some().unwrap_u8(), 1u8);
+    assert_eq!(tampered.decrypt(&sk).is_some().unwrap_u8(), 0u8);
+
+    let mut valid_times = Vec::with_capacity(SAMPLES);
+    let mut tampered_times = Vec::with_capacity(SAMPLES);
+    // Interleave the two classes so drift affects both equally
+    for i in 0..SAMPLES * 2 {
+        let (ciphertext, times) = if i % 2 == 0 {
+            (&valid, &mut valid_times)
+        } else {
+            (&tampered, &mut tampered_times)
+        };
+        let start = Instant::now();
+        let plaintext = std::hint::black_box(ciphertext.decrypt(std::hint::black_box(&sk)));
+        times.push(start.elapsed().as_nanos() as f64);
+        drop(plaintext);
+    }
+
+    let t = welch_t(&valid_times, &tampered_times);
+    assert!(
+        t.abs() < T_THRESHOLD,
+        "decrypting a tampered ciphertext takes a different time, t = {}",
+        t
+    );
+}
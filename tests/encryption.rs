@@ -1,5 +1,6 @@
 mod utils;
 use blsful::*;
+use rand_core::SeedableRng;
 use rstest::*;
 use utils::*;
 
@@ -203,3 +204,725 @@ fn elgamal_proofs_work<C: BlsSignatureImpl>(#[case] _c: C) {
         <C as BlsElGamal>::message_generator() * secret.0
     );
 }
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_decryption_key_with_config<C: BlsSignatureImpl>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let shares = sk.split(3, 5).unwrap();
+    let ciphertext = pk.sign_crypt(SignatureSchemes::Basic, TEST_MSG);
+    let decryption_shares = shares
+        .iter()
+        .map(|s| ciphertext.create_decryption_share(s).unwrap())
+        .collect::<Vec<_>>();
+
+    // Exactly the threshold
+    let config = ThresholdConfig::new(3, 5).unwrap();
+    let key =
+        SignCryptDecryptionKey::from_shares_with_config(&decryption_shares[..3], config).unwrap();
+    let plaintext = key.decrypt(&ciphertext);
+    assert_eq!(plaintext.is_some().unwrap_u8(), 1u8);
+    assert_eq!(plaintext.unwrap().as_slice(), TEST_MSG);
+
+    // Below the threshold
+    let res = SignCryptDecryptionKey::from_shares_with_config(&decryption_shares[..2], config);
+    assert!(matches!(
+        res,
+        Err(BlsError::InsufficientShares {
+            threshold: 3,
+            received: 2
+        })
+    ));
+
+    // Share index 5 exceeds a limit of 4
+    let config = ThresholdConfig::new(3, 4).unwrap();
+    let res = SignCryptDecryptionKey::from_shares_with_config(&decryption_shares[2..], config);
+    assert!(matches!(res, Err(BlsError::InvalidInputs(_))));
+
+    assert!(ThresholdConfig::new(1, 4).is_err());
+    assert!(ThresholdConfig::new(5, 4).is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_routing_tag<C: BlsSignatureImpl>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let ciphertext = pk.sign_crypt(SignatureSchemes::ProofOfPossession, TEST_MSG);
+
+    let tag = ciphertext.verify_and_extract_ephemeral().unwrap();
+    assert_eq!(tag, ciphertext.verify_and_extract_ephemeral().unwrap());
+    let other = pk.sign_crypt(SignatureSchemes::ProofOfPossession, TEST_MSG);
+    assert_ne!(tag, other.verify_and_extract_ephemeral().unwrap());
+
+    let mut tampered = SignCryptCiphertext {
+        v: ciphertext.v.clone(),
+        ..ciphertext
+    };
+    tampered.v[0] ^= 1;
+    assert!(tampered.verify_and_extract_ephemeral().is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_envelope_versioning<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let ciphertext = pk.sign_crypt(SignatureSchemes::ProofOfPossession, TEST_MSG);
+
+    let bytes = Vec::from(&ciphertext);
+    assert_eq!(bytes[0], 1);
+    // The empty extension area is encoded as a zero length
+    assert_eq!(bytes[bytes.len() - 1], 0);
+    let decoded = SignCryptCiphertext::<C>::try_from(&bytes).unwrap();
+    assert_eq!(decoded, ciphertext);
+
+    // Unknown extensions in the same version are skipped
+    let mut extended = bytes.clone();
+    extended.pop();
+    extended.extend_from_slice(&[3, 0xaa, 0xbb, 0xcc]);
+    let decoded = SignCryptCiphertext::<C>::try_from(&extended).unwrap();
+    assert_eq!(decoded, ciphertext);
+    assert_eq!(decoded.decrypt(&sk).unwrap(), TEST_MSG);
+
+    // A newer version is rejected instead of misparsed
+    let mut v2 = vec![2u8, 0xff, 0xff];
+    v2.extend_from_slice(&bytes[1..]);
+    let res = SignCryptCiphertext::<C>::try_from(&v2);
+    assert!(matches!(res, Err(BlsError::UnsupportedVersion(2))));
+
+    #[cfg(feature = "serde")]
+    {
+        let mut v2 = bytes.clone();
+        v2[0] = 2;
+        let res = serde_bare::from_slice::<SignCryptCiphertext<C>>(&v2);
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("unsupported version: 2"));
+
+        let json = serde_json::to_string(&ciphertext).unwrap();
+        let decoded: SignCryptCiphertext<C> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, ciphertext);
+    }
+}
+
+/// A ciphertext serialized before the versioned envelope was added
+#[test]
+fn sign_crypt_decodes_pre_envelope_layout() {
+    const LEGACY: &str = "a00b1981dfab6741d03a91d876d11805af938f0419f1022566c687ad2c8193f3b2972fbcb9df94c562a5e7d9ebf82f862089074ffd1c209eaf61bc6ce79ed67e302df4195de96a9f03e9e705d4dd3ba58f935fe51651ad82c19e6cba760b5e5fb8bce782090c5b4d2b904c1598265617cd8c3a6b830dd59f122a24637d783046140038a7f8612fa92ff84ab6584ebde51ebfdbfdd12bc7e91335d6df315a10d8257f4f3a78b4c34894f88696df8a70df2902";
+    let sk = SecretKey::<Bls12381G2Impl>::from_hash(b"legacy signcrypt");
+
+    let bytes = hex::decode(LEGACY).unwrap();
+    let ciphertext = SignCryptCiphertext::<Bls12381G2Impl>::try_from(&bytes).unwrap();
+    assert_eq!(ciphertext.scheme, SignatureSchemes::ProofOfPossession);
+    assert!(ciphertext.header.is_empty());
+    assert_eq!(ciphertext.decrypt(&sk).unwrap(), b"legacy message");
+
+    // It's written back in the current envelope
+    let reencoded = Vec::from(&ciphertext);
+    assert_eq!(reencoded[0], 1);
+    assert_eq!(
+        SignCryptCiphertext::<Bls12381G2Impl>::try_from(&reencoded).unwrap(),
+        ciphertext
+    );
+
+    #[cfg(feature = "serde")]
+    {
+        const LEGACY_JSON: &str = r#"{"u":"a00b1981dfab6741d03a91d876d11805af938f0419f1022566c687ad2c8193f3b2972fbcb9df94c562a5e7d9ebf82f86","v":[137,7,79,253,28,32,158,175,97,188,108,231,158,214,126,48,45,244,25,93,233,106,159,3,233,231,5,212,221,59,165,143],"w":"935fe51651ad82c19e6cba760b5e5fb8bce782090c5b4d2b904c1598265617cd8c3a6b830dd59f122a24637d783046140038a7f8612fa92ff84ab6584ebde51ebfdbfdd12bc7e91335d6df315a10d8257f4f3a78b4c34894f88696df8a70df29","scheme":"ProofOfPossession"}"#;
+        let decoded: SignCryptCiphertext<Bls12381G2Impl> =
+            serde_json::from_str(LEGACY_JSON).unwrap();
+        assert_eq!(decoded, ciphertext);
+    }
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn errors_include_operation_context<C: BlsSignatureImpl + std::fmt::Debug>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let ciphertext = pk.sign_crypt(SignatureSchemes::ProofOfPossession, TEST_MSG);
+    assert_eq!(ciphertext.try_decrypt(&sk).unwrap(), TEST_MSG);
+
+    let mut tampered = SignCryptCiphertext {
+        v: ciphertext.v.clone(),
+        ..ciphertext
+    };
+    tampered.v[0] ^= 1;
+    let err = tampered.try_decrypt(&sk).unwrap_err();
+    assert_eq!(err.operation(), Some("signcrypt unseal"));
+    assert!(err.to_string().starts_with("signcrypt unseal failed"));
+
+    let shares = sk.split(2, 3).unwrap();
+    let decryption_shares = shares
+        .iter()
+        .map(|s| tampered.create_decryption_share(s).unwrap())
+        .collect::<Vec<_>>();
+    let err = tampered
+        .try_decrypt_with_shares(&decryption_shares)
+        .unwrap_err();
+    assert!(err.to_string().starts_with("signcrypt unseal failed"));
+
+    let duplicates = [decryption_shares[0].clone(), decryption_shares[0].clone()];
+    let err = SignCryptDecryptionKey::from_shares(&duplicates).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("signcrypt decryption key combination failed"));
+
+    let sig_share = shares[0]
+        .sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
+        .unwrap();
+    let err = Signature::from_shares(&[sig_share, sig_share]).unwrap_err();
+    assert_eq!(err.operation(), Some("signature share combination"));
+    assert!(!matches!(err.root(), BlsError::Context { .. }));
+    assert!(err
+        .to_string()
+        .starts_with("signature share combination failed"));
+
+    let err = SecretKey::combine(&[shares[1].clone(), shares[1].clone()]).unwrap_err();
+    assert!(matches!(err.root(), BlsError::VsssError));
+    assert!(err
+        .to_string()
+        .starts_with("secret key share combination failed"));
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_detached_v<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let mut header = pk.sign_crypt(SignatureSchemes::ProofOfPossession, TEST_MSG);
+    let v = header.detach_v();
+    assert!(header.v.is_empty());
+
+    let bytes = Vec::from(&header);
+    let header = SignCryptCiphertext::<C>::try_from(bytes).unwrap();
+    let plaintext = header.decrypt_detached(&sk, &v).unwrap();
+    assert_eq!(plaintext.as_slice(), TEST_MSG);
+
+    let mut tampered = v.clone();
+    tampered[0] ^= 1;
+    assert_eq!(
+        header
+            .decrypt_detached(&sk, &tampered)
+            .is_none()
+            .unwrap_u8(),
+        1u8
+    );
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn time_lock_to_target<C: BlsSignatureImpl>(#[case] _c: C) {
+    let beacon = SecretKey::<C>::new();
+    let beacon_pk = beacon.public_key();
+    let target = TimeLockTarget::Round(1_000);
+    let ciphertext = beacon_pk
+        .time_lock(
+            TEST_MSG,
+            target.clone(),
+            SignatureSchemes::ProofOfPossession,
+        )
+        .unwrap();
+
+    let round_sig = beacon
+        .sign(SignatureSchemes::ProofOfPossession, &target.to_bytes())
+        .unwrap();
+    let plaintext = ciphertext.decrypt(&round_sig).unwrap();
+    assert_eq!(plaintext.as_slice(), TEST_MSG);
+
+    let early_sig = beacon
+        .sign(
+            SignatureSchemes::ProofOfPossession,
+            &TimeLockTarget::Round(999).to_bytes(),
+        )
+        .unwrap();
+    assert_eq!(ciphertext.decrypt(&early_sig).is_some().unwrap_u8(), 0u8);
+}
+
+/// Signcrypt ciphertexts created with a fixed key, plaintext and ephemeral seed.
+///
+/// If the signcrypt construction or its encoding changes these must be
+/// regenerated deliberately, never to make a failing test pass.
+const SIGN_CRYPT_VECTORS_G1: [(SignatureSchemes, &str); 3] = [
+    (
+        SignatureSchemes::Basic,
+        concat!(
+            "01b62c6878425a0f4558d65a9d1e63478265756a39869211b581aa9fa72532e1a402d2c9",
+            "858a52993e51381ca0edf68ba40fb31a770e09d6a96bcbd65a366672b8b026ef08c02108",
+            "6de09dafb7a041c8e8993f9d2952ad0c8680bef7edadc4534e20d9ff816903b5585c653d",
+            "c9e8deb531bab9554d03849fcb38a62cd98befc7315daef24b1a9be2b62bfeb758486eaa",
+            "06e828a9e101514a8c0ec9b00b154d2f0266f8e6663c67677102e98b44907c87dec00000",
+        ),
+    ),
+    (
+        SignatureSchemes::MessageAugmentation,
+        concat!(
+            "01b62c6878425a0f4558d65a9d1e63478265756a39869211b581aa9fa72532e1a402d2c9",
+            "858a52993e51381ca0edf68ba40fb31a770e09d6a96bcbd65a366672b8b026ef08c02108",
+            "6de09dafb7a041c8e8993f9d2952ad0c8680bef7edadc4534e20d9ff816903b5585c653d",
+            "c9e8deb531bab9554d03849fcb38a62cd98befc7315daa0cfcaba3b28299de3ff181a21c",
+            "bc056c4f812033a8f0dcb4baa56cf43811658dfe94f8b7ff160cb1663dba09ace9080100",
+        ),
+    ),
+    (
+        SignatureSchemes::ProofOfPossession,
+        concat!(
+            "01b62c6878425a0f4558d65a9d1e63478265756a39869211b581aa9fa72532e1a402d2c9",
+            "858a52993e51381ca0edf68ba40fb31a770e09d6a96bcbd65a366672b8b026ef08c02108",
+            "6de09dafb7a041c8e8993f9d2952ad0c8680bef7edadc4534e20d9ff816903b5585c653d",
+            "c9e8deb531bab9554d03849fcb38a62cd98befc7315da3e06872c9bc16313cd607e8b5d3",
+            "2d31453e25deb32021f26ed4900242beb049586ba125a9887dbad9f7e46934fa53a40200",
+        ),
+    ),
+];
+
+const SIGN_CRYPT_VECTORS_G2: [(SignatureSchemes, &str); 3] = [
+    (
+        SignatureSchemes::Basic,
+        concat!(
+            "01b58823a667ffd4d6aa7ffda52eccdb62fd84d7920bc3fc763c59c976b85924807c5c69",
+            "0b782aecaa45cca907ed53b1c4202b295d73deab8bc00c7183e73a3f38f74dd1593524a2",
+            "fa288d2879a49ce5323f86f09567d034aa4f4d93f0ed70f5ad2b0c6e4cd12d8848e2a08a",
+            "650812d15fb76904cd30f68b10fda975f0b205a0401d07659e4dd49302ccb79bb7f8fa67",
+            "2fd56853c0403ea6003d92ed7c9039cbb8f37d85ec0b239c0ead9bfa6df517a3f4090000",
+        ),
+    ),
+    (
+        SignatureSchemes::MessageAugmentation,
+        concat!(
+            "01b58823a667ffd4d6aa7ffda52eccdb62fd84d7920bc3fc763c59c976b85924807c5c69",
+            "0b782aecaa45cca907ed53b1c4202b295d73deab8bc00c7183e73a3f38f74dd1593524a2",
+            "fa288d2879a49ce5323fa88578046446481ccac5af03ba4bbfe6459ce757dedc80ec34f8",
+            "8cfdf2481b8c4c9fe383c05cbb7bc1450dc2b819d9860673eec15cbc42ed20bd8800df73",
+            "57ef7ea19494c1bc82f986fc3aefb75d20bc0b113fe97f776ef836da2394d0d0ccfd0100",
+        ),
+    ),
+    (
+        SignatureSchemes::ProofOfPossession,
+        concat!(
+            "01b58823a667ffd4d6aa7ffda52eccdb62fd84d7920bc3fc763c59c976b85924807c5c69",
+            "0b782aecaa45cca907ed53b1c4202b295d73deab8bc00c7183e73a3f38f74dd1593524a2",
+            "fa288d2879a49ce5323f8542b4bad5d87f49232f2462f6cb314b8f6f3bae9f5f3906bfa1",
+            "7e908e052b32536c55269cd8d9ca96ae874de1054041199cbe81697b3c4a33a18cfcb8bc",
+            "2ccfab70f4fd03dbed35687568681172bede810d74f77a52ab62279e443e7f5ecce20200",
+        ),
+    ),
+];
+
+#[rstest]
+#[case::g1(Bls12381G1Impl, SIGN_CRYPT_VECTORS_G1)]
+#[case::g2(Bls12381G2Impl, SIGN_CRYPT_VECTORS_G2)]
+fn sign_crypt_test_vectors<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+    #[case] vectors: [(SignatureSchemes, &str); 3],
+) {
+    let sk = SecretKey::<C>::from_hash(b"signcrypt test vector key");
+    let pk = sk.public_key();
+    for (scheme, expected) in vectors {
+        let expected = hex::decode(expected).unwrap();
+        let ciphertext = pk.sign_crypt_with_rng(scheme, TEST_MSG, MockRng::from_seed([3u8; 16]));
+        assert_eq!(
+            Vec::from(&ciphertext),
+            expected,
+            "{scheme} encryption changed"
+        );
+
+        let decoded = SignCryptCiphertext::<C>::try_from(expected).unwrap();
+        assert_eq!(decoded.scheme, scheme);
+        assert_eq!(decoded.decrypt(&sk).unwrap(), TEST_MSG);
+    }
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_keyring<C: BlsSignatureImpl + Clone>(#[case] _c: C) {
+    // A wrong key can occasionally unseal to garbage so fixed keys and
+    // randomness are used to keep the outcome deterministic
+    let keyring = [
+        SecretKey::<C>::from_hash(b"keyring key 0"),
+        SecretKey::<C>::from_hash(b"keyring key 1"),
+        SecretKey::<C>::from_hash(b"keyring key 2"),
+    ];
+    let ciphertext = keyring[1].public_key().sign_crypt_with_rng(
+        SignatureSchemes::ProofOfPossession,
+        TEST_MSG,
+        MockRng::from_seed([6u8; 16]),
+    );
+
+    let (index, plaintext) = ciphertext.try_decrypt_with_keyring(&keyring).unwrap();
+    assert_eq!(index, 1);
+    assert_eq!(plaintext.as_slice(), TEST_MSG);
+
+    assert!(ciphertext
+        .try_decrypt_with_keyring(&[keyring[0].clone(), keyring[2].clone()])
+        .is_none());
+    assert!(ciphertext.try_decrypt_with_keyring(&[]).is_none());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_decryption_key_raw_point<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let shares = sk.split(3, 5).unwrap();
+    let ciphertext = pk.sign_crypt(SignatureSchemes::Basic, TEST_MSG);
+    let decryption_shares = shares
+        .iter()
+        .map(|s| ciphertext.create_decryption_share(s).unwrap())
+        .collect::<Vec<_>>();
+    let key = SignCryptDecryptionKey::from_shares(&decryption_shares[..3]).unwrap();
+
+    assert_eq!(*key.as_raw_point(), ciphertext.u * sk.0);
+    assert_eq!(key.decrypt(&ciphertext).unwrap(), TEST_MSG);
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_seal_batch<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let msgs: [&[u8]; 4] = [
+        TEST_MSG,
+        TEST_MSG,
+        b"",
+        b"a much longer message than the others",
+    ];
+
+    let ciphertexts = pk.seal_batch(&msgs, SignatureSchemes::ProofOfPossession);
+    assert_eq!(ciphertexts.len(), msgs.len());
+    for (ciphertext, msg) in ciphertexts.iter().zip(msgs) {
+        assert_eq!(ciphertext.scheme, SignatureSchemes::ProofOfPossession);
+        assert_eq!(ciphertext.decrypt(&sk).unwrap().as_slice(), msg);
+    }
+
+    // Every message has its own ephemeral key, even for repeated messages
+    for (i, a) in ciphertexts.iter().enumerate() {
+        for b in &ciphertexts[i + 1..] {
+            assert_ne!(a.u, b.u);
+        }
+    }
+    assert_ne!(ciphertexts[0].v, ciphertexts[1].v);
+
+    // The shared precomputation gives the same ciphertexts as sealing one by one
+    let mut rng = MockRng::from_seed([5u8; 16]);
+    let batch = pk.seal_batch_with_rng(&msgs, SignatureSchemes::Basic, &mut rng);
+    let mut rng = MockRng::from_seed([5u8; 16]);
+    for (ciphertext, msg) in batch.iter().zip(msgs) {
+        let single = pk.sign_crypt_with_rng(SignatureSchemes::Basic, msg, &mut rng);
+        assert_eq!(ciphertext.u, single.u);
+        assert_eq!(ciphertext.v, single.v);
+        assert_eq!(ciphertext.w, single.w);
+    }
+
+    assert!(pk.seal_batch(&[], SignatureSchemes::Basic).is_empty());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_seal_padded<C: BlsSignatureImpl + PartialEq + Eq + Clone + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let msgs: [&[u8]; 4] = [b"", b"short", TEST_MSG, &[7u8; 200]];
+
+    let ciphertexts = msgs
+        .iter()
+        .map(|msg| {
+            pk.seal_padded(SignatureSchemes::ProofOfPossession, msg, 256)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    for (ciphertext, msg) in ciphertexts.iter().zip(msgs) {
+        assert_eq!(ciphertext.v.len(), 256);
+        assert_eq!(ciphertext.decrypt(&sk).unwrap().as_slice(), msg);
+    }
+
+    // A message that doesn't fit in one block takes the next
+    let ciphertext = pk
+        .seal_padded(SignatureSchemes::Basic, [7u8; 300], 256)
+        .unwrap();
+    assert_eq!(ciphertext.v.len(), 512);
+    assert_eq!(ciphertext.decrypt(&sk).unwrap().as_slice(), &[7u8; 300]);
+
+    // The padding is authenticated
+    let mut tampered = ciphertexts[1].clone();
+    tampered.v.truncate(128);
+    assert!(bool::from(tampered.decrypt(&sk).is_none()));
+    let mut tampered = ciphertexts[1].clone();
+    let last = tampered.v.len() - 1;
+    tampered.v[last] ^= 1;
+    assert!(bool::from(tampered.decrypt(&sk).is_none()));
+
+    assert!(pk
+        .seal_padded(SignatureSchemes::Basic, TEST_MSG, 0)
+        .is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_check_integrity<C: BlsSignatureImpl + PartialEq + Eq + Clone + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    let pk = SecretKey::<C>::from_hash(b"check integrity").public_key();
+    let ciphertext = pk.sign_crypt_with_rng(
+        SignatureSchemes::Basic,
+        TEST_MSG,
+        MockRng::from_seed([3u8; 16]),
+    );
+    let bytes = Vec::from(&ciphertext);
+    let check = SignCryptCiphertext::<C>::check_integrity;
+
+    assert_eq!(
+        check(&bytes),
+        CiphertextIntegrity {
+            header_ok: true,
+            payload_ok: true
+        }
+    );
+    assert!(check(&bytes).is_intact());
+
+    // payload
+    let mut tampered = ciphertext.clone();
+    tampered.v[5] ^= 1;
+    assert_eq!(
+        check(&Vec::from(&tampered)),
+        CiphertextIntegrity {
+            header_ok: true,
+            payload_ok: false
+        }
+    );
+    let mut tampered = ciphertext.clone();
+    tampered.v.push(0);
+    assert_eq!(
+        check(&Vec::from(&tampered)),
+        CiphertextIntegrity {
+            header_ok: true,
+            payload_ok: false
+        }
+    );
+
+    // scheme
+    let mut tampered = ciphertext.clone();
+    tampered.scheme = SignatureSchemes::ProofOfPossession;
+    assert_eq!(
+        check(&Vec::from(&tampered)),
+        CiphertextIntegrity {
+            header_ok: false,
+            payload_ok: true
+        }
+    );
+
+    // version
+    let mut tampered = bytes.clone();
+    tampered[0] = 7;
+    assert_eq!(
+        check(&tampered),
+        CiphertextIntegrity {
+            header_ok: false,
+            payload_ok: true
+        }
+    );
+
+    // u
+    let mut tampered = bytes.clone();
+    tampered[10] ^= 1;
+    assert!(!check(&tampered).header_ok);
+
+    // w, which is followed by the scheme byte and empty extensions
+    let mut tampered = bytes.clone();
+    let len = tampered.len();
+    tampered[len - 10] ^= 1;
+    assert!(!check(&tampered).header_ok);
+
+    assert!(!check(&[]).header_ok);
+    assert!(!check(&bytes[..bytes.len() - 1]).is_intact());
+}
+
+#[rstest]
+#[case::g1_basic(Bls12381G1Impl, SignatureSchemes::Basic)]
+#[case::g1_aug(Bls12381G1Impl, SignatureSchemes::MessageAugmentation)]
+#[case::g1_pop(Bls12381G1Impl, SignatureSchemes::ProofOfPossession)]
+#[case::g2_basic(Bls12381G2Impl, SignatureSchemes::Basic)]
+#[case::g2_aug(Bls12381G2Impl, SignatureSchemes::MessageAugmentation)]
+#[case::g2_pop(Bls12381G2Impl, SignatureSchemes::ProofOfPossession)]
+fn sign_crypt_to_group_public_key<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+    #[case] scheme: SignatureSchemes,
+) {
+    // The group key is only known through the members' public key shares
+    let shares = SecretKey::<C>::new().split(3, 5).unwrap();
+    let pk_shares = shares
+        .iter()
+        .map(|s| s.public_key().unwrap())
+        .collect::<Vec<_>>();
+    let group_pk = PublicKey::from_shares(&pk_shares[..3]).unwrap();
+
+    let ciphertext = group_pk.sign_crypt(scheme, TEST_MSG);
+    assert_eq!(ciphertext.is_valid().unwrap_u8(), 1u8);
+
+    let decryption_shares = shares
+        .iter()
+        .map(|s| ciphertext.create_decryption_share(s).unwrap())
+        .collect::<Vec<_>>();
+    for (share, pk_share) in decryption_shares.iter().zip(&pk_shares) {
+        assert!(share.verify(pk_share, &ciphertext).is_ok());
+    }
+    assert!(decryption_shares[0]
+        .verify(&pk_shares[1], &ciphertext)
+        .is_err());
+
+    for members in [&decryption_shares[..3], &decryption_shares[2..]] {
+        let plaintext = ciphertext.decrypt_with_shares(members);
+        assert_eq!(plaintext.is_some().unwrap_u8(), 1u8);
+        assert_eq!(plaintext.unwrap().as_slice(), TEST_MSG);
+    }
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn signature_and_decryption_shares_combine_separately<
+    C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug,
+>(
+    #[case] _c: C,
+) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let shares = sk.split(2, 3).unwrap();
+    let ciphertext = pk.sign_crypt(SignatureSchemes::ProofOfPossession, TEST_MSG);
+
+    // Partial signatures combine into a signature
+    let sig_shares = shares
+        .iter()
+        .map(|s| {
+            s.sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let sig = Signature::from_shares(&sig_shares).unwrap();
+    assert!(sig.verify(&pk, TEST_MSG).is_ok());
+
+    // Decryption shares combine into a decryption key for this ciphertext
+    let decryption_shares = shares
+        .iter()
+        .map(|s| ciphertext.create_decryption_share(s).unwrap())
+        .collect::<Vec<_>>();
+    let key = SignCryptDecryptionKey::from_shares(&decryption_shares).unwrap();
+    assert_eq!(key.decrypt(&ciphertext).unwrap().as_slice(), TEST_MSG);
+    assert_eq!(
+        ciphertext
+            .decrypt_with_shares(&decryption_shares)
+            .unwrap()
+            .as_slice(),
+        TEST_MSG
+    );
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_default_is_invalid<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    let ciphertext = SignCryptCiphertext::<C>::default();
+    assert_eq!(ciphertext.is_valid().unwrap_u8(), 0u8);
+    assert!(ciphertext.verify_and_extract_ephemeral().is_err());
+
+    let sk = SecretKey::<C>::new();
+    assert_eq!(ciphertext.decrypt(&sk).is_some().unwrap_u8(), 0u8);
+    assert!(ciphertext.try_decrypt(&sk).is_err());
+    let shares = sk.split(2, 3).unwrap();
+    assert!(ciphertext.create_decryption_share(&shares[0]).is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_crypt_with_header<C: BlsSignatureImpl + PartialEq + Eq + Clone + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    const HEADER: &[u8] = b"recipient=alice;content-type=text/plain";
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+
+    for scheme in [
+        SignatureSchemes::Basic,
+        SignatureSchemes::MessageAugmentation,
+        SignatureSchemes::ProofOfPossession,
+    ] {
+        let ciphertext = pk.sign_crypt_with_header(scheme, TEST_MSG, HEADER);
+        assert_eq!(ciphertext.header, HEADER);
+        assert_eq!(ciphertext.is_valid().unwrap_u8(), 1u8);
+        assert_eq!(ciphertext.decrypt(&sk).unwrap(), TEST_MSG);
+
+        // The header survives serialization
+        let bytes = Vec::from(&ciphertext);
+        let decoded = SignCryptCiphertext::<C>::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, ciphertext);
+        assert!(SignCryptCiphertext::<C>::check_integrity(&bytes).is_intact());
+
+        // Group decryption authenticates the header too
+        let shares = sk.split(2, 3).unwrap();
+        let dec_shares = shares
+            .iter()
+            .map(|s| ciphertext.create_decryption_share(s).unwrap())
+            .collect::<Vec<_>>();
+        for (s, d) in shares.iter().zip(&dec_shares) {
+            assert!(d.verify(&s.public_key().unwrap(), &ciphertext).is_ok());
+        }
+        assert_eq!(
+            ciphertext.decrypt_with_shares(&dec_shares[..2]).unwrap(),
+            TEST_MSG
+        );
+
+        let mut tampered = ciphertext.clone();
+        tampered.header[0] ^= 1;
+        assert_eq!(tampered.is_valid().unwrap_u8(), 0u8);
+        assert_eq!(tampered.decrypt(&sk).is_some().unwrap_u8(), 0u8);
+        assert_eq!(
+            tampered
+                .decrypt_with_shares(&dec_shares[..2])
+                .is_some()
+                .unwrap_u8(),
+            0u8
+        );
+
+        let mut stripped = ciphertext.clone();
+        stripped.header.clear();
+        assert_eq!(stripped.is_valid().unwrap_u8(), 0u8);
+        assert_eq!(stripped.decrypt(&sk).is_some().unwrap_u8(), 0u8);
+        let bytes = Vec::from(&stripped);
+        assert!(!SignCryptCiphertext::<C>::check_integrity(&bytes).is_intact());
+
+        // A header can't be attached to a ciphertext made without one
+        let mut attached = pk.sign_crypt(scheme, TEST_MSG);
+        attached.header = HEADER.to_vec();
+        assert_eq!(attached.is_valid().unwrap_u8(), 0u8);
+
+        // An empty header is the same as not having one
+        let ciphertext = pk.sign_crypt_with_header(scheme, TEST_MSG, []);
+        assert_eq!(ciphertext.is_valid().unwrap_u8(), 1u8);
+        assert_eq!(
+            Vec::from(&ciphertext).len(),
+            Vec::from(&pk.sign_crypt(scheme, TEST_MSG)).len()
+        );
+    }
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "testing")]
+
+use blsful::testing::*;
+use blsful::*;
+use proptest::prelude::*;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn secret_keys_round_trip(sk in secret_key::<Bls12381G1Impl>()) {
+        bytes_round_trip(&sk)?;
+        serde_round_trip(&sk)?;
+    }
+
+    #[test]
+    fn public_keys_round_trip(pk in public_key::<Bls12381G2Impl>()) {
+        bytes_round_trip(&pk)?;
+        serde_round_trip(&pk)?;
+    }
+
+    #[test]
+    fn signatures_round_trip(sig in signature::<Bls12381G1Impl>()) {
+        bytes_round_trip(&sig)?;
+        serde_round_trip(&sig)?;
+    }
+
+    #[test]
+    fn sign_crypt_ciphertexts_round_trip(
+        ciphertext in sign_crypt_ciphertext::<Bls12381G2Impl>()
+    ) {
+        bytes_round_trip(&ciphertext)?;
+        serde_round_trip(&ciphertext)?;
+    }
+
+    #[test]
+    fn signatures_verify(sk in secret_key::<Bls12381G2Impl>(), scheme in scheme(), msg in message()) {
+        let sig = sk.sign(scheme, &msg).unwrap();
+        prop_assert_eq!(sig.scheme(), scheme);
+        prop_assert!(sig.verify(&sk.public_key(), &msg).is_ok());
+    }
+}
+
+#[test]
+fn deterministic_committee_is_reproducible() {
+    const MSG: &[u8] = b"deterministic committee";
+
+    let (pk, shares) = deterministic_committee::<Bls12381G2Impl>([7u8; 32], 3, 5);
+    let (pk2, shares2) = deterministic_committee::<Bls12381G2Impl>([7u8; 32], 3, 5);
+    assert_eq!(pk, pk2);
+    assert_eq!(shares, shares2);
+    let (pk3, _) = deterministic_committee::<Bls12381G2Impl>([8u8; 32], 3, 5);
+    assert_ne!(pk, pk3);
+
+    for signers in [&shares[..3], &shares[2..]] {
+        let sig_shares = signers
+            .iter()
+            .map(|s| s.sign(SignatureSchemes::ProofOfPossession, MSG).unwrap())
+            .collect::<Vec<_>>();
+        let sig = Signature::from_shares(&sig_shares).unwrap();
+        assert!(sig.verify(&pk, MSG).is_ok());
+    }
+}
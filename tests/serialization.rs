@@ -1,10 +1,11 @@
 mod utils;
 
-use blsful::inner_types::{G1Projective, G2Projective};
+use blsful::inner_types::{G1Projective, G2Projective, Group};
 use blsful::*;
 use rstest::*;
 use utils::*;
 
+#[cfg(feature = "serde")]
 #[rstest]
 #[case::g1(Bls12381G1Impl)]
 #[case::g2(Bls12381G2Impl)]
@@ -62,6 +63,7 @@ fn basic_types_serialize_json<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::D
     assert_eq!(sig_pop, sig_pop2);
 }
 
+#[cfg(feature = "serde")]
 #[rstest]
 #[case::g1(Bls12381G1Impl)]
 #[case::g2(Bls12381G2Impl)]
@@ -119,6 +121,7 @@ fn basic_types_serialize_binary<C: BlsSignatureImpl + PartialEq + Eq + std::fmt:
     assert_eq!(sig_pop, sig_pop2);
 }
 
+#[cfg(feature = "serde")]
 #[rstest]
 #[case::g1(Bls12381G1Impl)]
 #[case::g2(Bls12381G2Impl)]
@@ -137,45 +140,39 @@ fn shares_serialize<
     let sk_shares = sk.split(10, 20).unwrap();
     for share in &sk_shares {
         let res = serde_json::to_vec(&share);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let text = res.unwrap();
         let res = serde_json::from_slice::<SecretKeyShare<C>>(&text);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let share2 = res.unwrap();
         assert_eq!(share, &share2);
 
         let res = serde_bare::to_vec(&share);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let text = res.unwrap();
         let res = serde_bare::from_slice::<SecretKeyShare<C>>(&text);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let share2 = res.unwrap();
         assert_eq!(share, &share2);
 
         let pks = share.public_key().unwrap();
         let res = serde_json::to_vec(&pks);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let text = res.unwrap();
         let res = serde_json::from_slice::<PublicKeyShare<C>>(&text);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let pks2 = res.unwrap();
         assert_eq!(pks, pks2);
 
@@ -192,6 +189,7 @@ fn shares_serialize<
     }
 }
 
+#[cfg(feature = "serde")]
 #[test]
 fn shares_serialize_test() {
     let sk = SecretKey::<Bls12381G1Impl>::from_hash(b"shares_serialize_json");
@@ -199,31 +197,27 @@ fn shares_serialize_test() {
     let sk_shares = sk.split(10, 20).unwrap();
     for share in &sk_shares {
         let res = serde_json::to_vec(&share);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let text = res.unwrap();
         let res = serde_json::from_slice::<SecretKeyShare<Bls12381G1Impl>>(&text);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let share2 = res.unwrap();
         assert_eq!(share, &share2);
 
         let pks = share.public_key().unwrap();
         let res = serde_json::to_vec(&pks);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let text = res.unwrap();
         let res = serde_json::from_slice::<PublicKeyShare<Bls12381G1Impl>>(&text);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let pks2 = res.unwrap();
         assert_eq!(pks, pks2);
 
@@ -250,10 +244,9 @@ fn legacy_shares_test() {
         v1[1..].copy_from_slice(&share.0.value.to_le_bytes());
 
         let res = SecretKeyShare::<Bls12381G1Impl>::from_v1_bytes(&v1);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let share2 = res.unwrap();
         assert_eq!(share, &share2);
 
@@ -263,10 +256,9 @@ fn legacy_shares_test() {
         v1[1..].copy_from_slice(&t.to_compressed());
 
         let res = InnerPointShareG1::from_v1_bytes(&v1);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let share2 = res.unwrap();
         assert_eq!(share.0.identifier, share2.0.identifier);
         assert_eq!(t, share2.0.value.0);
@@ -277,12 +269,408 @@ fn legacy_shares_test() {
         v1[1..].copy_from_slice(&t.to_compressed());
 
         let res = InnerPointShareG2::from_v1_bytes(&v1);
-        if res.is_err() {
-            assert!(false, "{:?}", res.unwrap_err());
+        if let Err(e) = &res {
+            panic!("{:?}", e);
         }
-        assert!(res.is_ok());
         let share2 = res.unwrap();
         assert_eq!(share.0.identifier, share2.0.identifier);
         assert_eq!(t, share2.0.value.0);
     }
 }
+
+#[cfg(feature = "serde")]
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn tagged_types_serialize_json<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    let sk = SecretKey::<C>::random(MockRng::default());
+    let pk = TaggedPublicKey(sk.public_key());
+    let sig = TaggedSignature(
+        sk.sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
+            .unwrap(),
+    );
+
+    let text = serde_json::to_string(&pk).unwrap();
+    let value = serde_json::from_str::<serde_json::Value>(&text).unwrap();
+    assert_eq!(value["curve"], C::NAME);
+    assert_eq!(value["key"], format!("0x{}", hex::encode(Vec::from(&pk.0))));
+    let pk2 = serde_json::from_str::<TaggedPublicKey<C>>(&text).unwrap();
+    assert_eq!(pk, pk2);
+
+    let text = serde_json::to_string(&sig).unwrap();
+    let value = serde_json::from_str::<serde_json::Value>(&text).unwrap();
+    assert_eq!(value["curve"], C::NAME);
+    let sig2 = serde_json::from_str::<TaggedSignature<C>>(&text).unwrap();
+    assert_eq!(sig, sig2);
+    assert!(sig2.0.verify(&pk2.0, TEST_MSG).is_ok());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn tagged_types_reject_wrong_curve() {
+    let sk = SecretKey::<Bls12381G1Impl>::random(MockRng::default());
+    let pk = TaggedPublicKey(sk.public_key());
+    let sig = TaggedSignature(sk.sign(SignatureSchemes::Basic, TEST_MSG).unwrap());
+
+    let text = serde_json::to_string(&pk)
+        .unwrap()
+        .replace(Bls12381G1Impl::NAME, Bls12381G2Impl::NAME);
+    let res = serde_json::from_str::<TaggedPublicKey<Bls12381G1Impl>>(&text);
+    assert!(res.unwrap_err().to_string().contains("invalid curve"));
+
+    let text = serde_json::to_string(&sig)
+        .unwrap()
+        .replace(Bls12381G1Impl::NAME, Bls12381G2Impl::NAME);
+    let res = serde_json::from_str::<TaggedSignature<Bls12381G1Impl>>(&text);
+    assert!(res.unwrap_err().to_string().contains("invalid curve"));
+}
+
+/// Find an encoding of a point on the curve that isn't in the prime order subgroup
+fn non_subgroup_public_key<C: BlsSignatureImpl>() -> PublicKey<C> {
+    let mut bytes = vec![0u8; Vec::from(&SecretKey::<C>::new().public_key()).len()];
+    bytes[0] = 0x80;
+    let last = bytes.len() - 1;
+    for i in 1..=u8::MAX {
+        bytes[last] = i;
+        if let Ok(pk) = PublicKey::<C>::from_bytes_unchecked(&bytes) {
+            assert!(PublicKey::<C>::try_from(&bytes).is_err());
+            return pk;
+        }
+    }
+    panic!("no point found");
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn public_key_batch_validate<C: BlsSignatureImpl>(#[case] _c: C) {
+    let mut keys = (0..20)
+        .map(|_| {
+            let pk = SecretKey::<C>::new().public_key();
+            PublicKey::<C>::from_bytes_unchecked(&Vec::from(&pk)).unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert!(PublicKey::batch_validate(&keys).is_ok());
+    assert!(PublicKey::<C>::batch_validate(&[]).is_ok());
+
+    keys[13] = non_subgroup_public_key::<C>();
+    let err = PublicKey::batch_validate(&keys).unwrap_err();
+    assert!(err.to_string().contains("index 13"));
+
+    keys[13] = PublicKey(<C as Pairing>::PublicKey::identity());
+    assert!(PublicKey::batch_validate(&keys).is_err());
+
+    assert!(PublicKey::<C>::from_bytes_unchecked(&[0u8; 3]).is_err());
+}
+
+#[cfg(feature = "secret-export")]
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn secret_key_hex_export<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(#[case] _c: C) {
+    let sk = SecretKey::<C>::random(MockRng::default());
+    let hex_str = sk.to_hex();
+    assert_eq!(hex_str.len(), SECRET_KEY_BYTES * 2);
+    assert_eq!(SecretKey::<C>::from_hex(&hex_str).unwrap(), sk);
+
+    assert!(SecretKey::<C>::from_hex(&"00".repeat(SECRET_KEY_BYTES)).is_err());
+    assert!(SecretKey::<C>::from_hex(&"ff".repeat(SECRET_KEY_BYTES)).is_err());
+    assert!(SecretKey::<C>::from_hex("abcd").is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn secret_key_rejects_zero<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(#[case] _c: C) {
+    use blsful::inner_types::Field;
+    use blsful::vsss_rs::{IdentifierPrimeField, Share};
+    type Scalar<C> = <<C as Pairing>::PublicKey as Group>::Scalar;
+
+    let zero = [0u8; SECRET_KEY_BYTES];
+    assert_eq!(
+        SecretKey::<C>::from_be_bytes(&zero).is_none().unwrap_u8(),
+        1u8
+    );
+    assert_eq!(
+        SecretKey::<C>::from_le_bytes(&zero).is_none().unwrap_u8(),
+        1u8
+    );
+    assert!(matches!(
+        SecretKey::<C>::try_from(&zero[..]),
+        Err(BlsError::InvalidSecretKey)
+    ));
+
+    let zero_key = SecretKey::<C>(Scalar::<C>::ZERO);
+    assert_eq!(zero_key.is_zero().unwrap_u8(), 1u8);
+    assert_eq!(SecretKey::<C>::new().is_zero().unwrap_u8(), 0u8);
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_string(&zero_key).unwrap();
+        assert!(serde_json::from_str::<SecretKey<C>>(&json).is_err());
+        let bare = serde_bare::to_vec(&zero_key).unwrap();
+        assert!(serde_bare::from_slice::<SecretKey<C>>(&bare).is_err());
+    }
+
+    let zero_shares = (1..=3u64)
+        .map(|i| {
+            SecretKeyShare::<C>(<C as Pairing>::SecretKeyShare::with_identifier_and_value(
+                IdentifierPrimeField(Scalar::<C>::from(i)),
+                IdentifierPrimeField(Scalar::<C>::ZERO),
+            ))
+        })
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        SecretKey::combine(&zero_shares),
+        Err(BlsError::Context { source, .. }) if matches!(*source, BlsError::InvalidSecretKey)
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn proofs_serialize<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug + Copy>(#[case] _c: C) {
+    let sk = SecretKey::<C>::random(MockRng::default());
+    let pk = sk.public_key();
+    let sig = sk
+        .sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
+        .unwrap();
+
+    let pop = sk.proof_of_possession().unwrap();
+    let (commitment, x) = ProofCommitment::generate(TEST_MSG, sig).unwrap();
+    let y = ProofCommitmentChallenge::<C>::new();
+    let pok = commitment.finalize(x, y, sig).unwrap();
+    let pok_timestamp = ProofOfKnowledgeTimestamp::generate(TEST_MSG, sig).unwrap();
+
+    let json = serde_json::to_string(&pop).unwrap();
+    let pop2 = serde_json::from_str::<ProofOfPossession<C>>(&json).unwrap();
+    assert!(pop2.verify(pk).is_ok());
+    let bare = serde_bare::to_vec(&pop).unwrap();
+    let pop2 = serde_bare::from_slice::<ProofOfPossession<C>>(&bare).unwrap();
+    assert!(pop2.verify(pk).is_ok());
+
+    let json = serde_json::to_string(&pok).unwrap();
+    let pok2 = serde_json::from_str::<ProofOfKnowledge<C>>(&json).unwrap();
+    assert!(pok2.verify(pk, TEST_MSG, y).is_ok());
+    let bare = serde_bare::to_vec(&pok).unwrap();
+    let pok2 = serde_bare::from_slice::<ProofOfKnowledge<C>>(&bare).unwrap();
+    assert!(pok2.verify(pk, TEST_MSG, y).is_ok());
+
+    let json = serde_json::to_string(&pok_timestamp).unwrap();
+    let pok_timestamp2 = serde_json::from_str::<ProofOfKnowledgeTimestamp<C>>(&json).unwrap();
+    assert!(pok_timestamp2.verify(pk, TEST_MSG, None).is_ok());
+    let bare = serde_bare::to_vec(&pok_timestamp).unwrap();
+    let pok_timestamp2 = serde_bare::from_slice::<ProofOfKnowledgeTimestamp<C>>(&bare).unwrap();
+    assert!(pok_timestamp2.verify(pk, TEST_MSG, None).is_ok());
+
+    // A point that isn't on the curve is rejected
+    let mut bare = serde_bare::to_vec(&pop).unwrap();
+    let last = bare.len() - 1;
+    bare[last] ^= 1;
+    assert!(serde_bare::from_slice::<ProofOfPossession<C>>(&bare).is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn signature_tagged_bytes<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(#[case] _c: C) {
+    let sk = SecretKey::<C>::random(MockRng::default());
+    let pk = sk.public_key();
+    for scheme in [
+        SignatureSchemes::Basic,
+        SignatureSchemes::MessageAugmentation,
+        SignatureSchemes::ProofOfPossession,
+    ] {
+        let sig = sk.sign(scheme, TEST_MSG).unwrap();
+        let bytes = sig.to_tagged_bytes();
+        assert_eq!(bytes[0], scheme as u8);
+        assert_eq!(bytes, Vec::from(&sig));
+
+        let (scheme2, sig2) = Signature::<C>::from_tagged_bytes(&bytes).unwrap();
+        assert_eq!(scheme2, scheme);
+        assert_eq!(sig2, sig);
+        assert!(sig2.verify(&pk, TEST_MSG).is_ok());
+    }
+
+    let mut bytes = sk
+        .sign(SignatureSchemes::Basic, TEST_MSG)
+        .unwrap()
+        .to_tagged_bytes();
+    bytes[0] = 3;
+    assert!(matches!(
+        Signature::<C>::from_tagged_bytes(&bytes),
+        Err(BlsError::InvalidSignatureScheme)
+    ));
+    assert!(Signature::<C>::from_tagged_bytes(&bytes[1..]).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn deserialization_error_source<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    use std::error::Error;
+
+    let ciphertext = SecretKey::<C>::new()
+        .public_key()
+        .sign_crypt(SignatureSchemes::ProofOfPossession, b"error source");
+    let bytes = Vec::from(&ciphertext);
+    let err = SignCryptCiphertext::<C>::try_from(&bytes[..bytes.len() - 3]).unwrap_err();
+    assert!(matches!(err, BlsError::SerializationFormat(_)));
+    assert!(err
+        .source()
+        .and_then(|e| e.downcast_ref::<serde_bare::error::Error>())
+        .is_some());
+
+    // The cause survives being wrapped
+    let err = err.context("decode ciphertext");
+    let inner = err.source().unwrap();
+    assert!(inner.downcast_ref::<BlsError>().is_some());
+    assert!(inner
+        .source()
+        .and_then(|e| e.downcast_ref::<serde_bare::error::Error>())
+        .is_some());
+    assert_eq!(anyhow::Error::from(err).chain().count(), 3);
+}
+
+/// The bincode encoding of a share's identifier, 1, as a length prefixed scalar
+#[cfg(feature = "serde")]
+const SHARE_IDENTIFIER_BINCODE: &str =
+    "20000000000000000100000000000000000000000000000000000000000000000000000000000000";
+
+#[cfg(feature = "serde")]
+#[rstest]
+#[case::g1(
+    Bls12381G1Impl,
+    concat!(
+        "6000000000000000",
+        "a6b64129008743a4348fd5c72659aaf9d9c1612594f7bd276c2eb4681207f13d",
+        "3c9369fe8db8ebf4e9aadf7c09cec20f178b38843122d13b2841dd71017faa2a",
+        "8e8faef845f8cc7ae0899584bfe18bde09fbc13e99c4b10fa9d9ce6764fc816e",
+    ),
+    concat!(
+        "6000000000000000",
+        "98c602cb53e0eb480f2a6fdb159822ed481bc9fa10e56c562d4907d37d415240",
+        "6c9a676eac237c13f1954f9af7fdf2c50c21db472bf12691028ace4554efb736",
+        "02a4a1b9b5ed177bb307db295d7aa9efdb6eb8fcc12198602ed2c03b31935061",
+    )
+)]
+#[case::g2(
+    Bls12381G2Impl,
+    concat!(
+        "3000000000000000",
+        "8b5574667160c72691007297039c5a38b1027b0876cf943f",
+        "1bb8db1c7174be5dda574bcd7e54bb4eeb609a1f7aa47ce9",
+    ),
+    concat!(
+        "3000000000000000",
+        "989185138e3669a6700a8bc1ea9cd51ff671e7c00d37b13c",
+        "903c8211f3a64e63c1b0add9f8ea1d037c12fee52a3106a1",
+    )
+)]
+fn shares_bincode_encoding_is_stable<
+    C: BlsSignatureImpl
+        + PartialEq
+        + Eq
+        + std::fmt::Debug
+        + serde::Serialize
+        + serde::de::DeserializeOwned,
+>(
+    #[case] _c: C,
+    #[case] public_key_share_value: &str,
+    #[case] decryption_share_value: &str,
+) {
+    // If any of these fail the wire format of the shares changed, which
+    // requires a major version bump
+    let sks_bytes = hex::decode(format!(
+        "{}{}",
+        SHARE_IDENTIFIER_BINCODE,
+        "20000000000000002ed0fa5c0c7eff2bd84b7d3252a015ea04fb6927ce9ba61effe4a88ce9b1f24d"
+    ))
+    .unwrap();
+    let sks: SecretKeyShare<C> = bincode::deserialize(&sks_bytes).unwrap();
+    assert_eq!(bincode::serialize(&sks).unwrap(), sks_bytes);
+
+    let pks_bytes = hex::decode(format!(
+        "{}{}",
+        SHARE_IDENTIFIER_BINCODE, public_key_share_value
+    ))
+    .unwrap();
+    let pks = sks.public_key().unwrap();
+    assert_eq!(bincode::serialize(&pks).unwrap(), pks_bytes);
+    assert_eq!(
+        bincode::deserialize::<PublicKeyShare<C>>(&pks_bytes).unwrap(),
+        pks
+    );
+
+    let ds_bytes = hex::decode(format!(
+        "{}{}",
+        SHARE_IDENTIFIER_BINCODE, decryption_share_value
+    ))
+    .unwrap();
+    let ciphertext = SignCryptCiphertext::<C> {
+        u: <C as Pairing>::PublicKey::generator().double(),
+        ..Default::default()
+    };
+    let ds = ciphertext.create_decryption_share(&sks).unwrap();
+    assert_eq!(bincode::serialize(&ds).unwrap(), ds_bytes);
+    assert!(bincode::deserialize::<SignDecryptionShare<C>>(&ds_bytes).unwrap() == ds);
+
+    // Shares decoded from bincode still combine
+    let sk = SecretKey::<C>::from_hash(b"bincode share conformance");
+    let shares = sk
+        .split(2, 3)
+        .unwrap()
+        .iter()
+        .map(|s| bincode::deserialize(&bincode::serialize(s).unwrap()).unwrap())
+        .collect::<Vec<SecretKeyShare<C>>>();
+    assert_eq!(SecretKey::combine(&shares[..2]).unwrap(), sk);
+    let pk_shares = shares
+        .iter()
+        .map(|s| {
+            let bytes = bincode::serialize(&s.public_key().unwrap()).unwrap();
+            bincode::deserialize(&bytes).unwrap()
+        })
+        .collect::<Vec<PublicKeyShare<C>>>();
+    assert_eq!(
+        PublicKey::from_shares(&pk_shares[1..]).unwrap(),
+        sk.public_key()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn kdf_params_deserialize_enforces_minimum() {
+    let params = KdfParams::new(4096).unwrap();
+    let json = serde_json::to_string(&params).unwrap();
+    assert_eq!(json, r#"{"iterations":4096}"#);
+    assert_eq!(serde_json::from_str::<KdfParams>(&json).unwrap(), params);
+    let bare = serde_bare::to_vec(&params).unwrap();
+    assert_eq!(serde_bare::from_slice::<KdfParams>(&bare).unwrap(), params);
+
+    assert!(serde_json::from_str::<KdfParams>(r#"{"iterations":0}"#).is_err());
+    assert!(serde_json::from_str::<KdfParams>(r#"{"iterations":1}"#).is_err());
+    let weak = serde_bare::to_vec(&1u32).unwrap();
+    assert!(serde_bare::from_slice::<KdfParams>(&weak).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn threshold_config_deserialize_validates() {
+    let config = ThresholdConfig::new(3, 5).unwrap();
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(json, r#"{"threshold":3,"limit":5}"#);
+    assert_eq!(
+        serde_json::from_str::<ThresholdConfig>(&json).unwrap(),
+        config
+    );
+    assert_eq!(config.threshold(), 3);
+    assert_eq!(config.limit(), 5);
+
+    assert!(serde_json::from_str::<ThresholdConfig>(r#"{"threshold":1,"limit":5}"#).is_err());
+    assert!(serde_json::from_str::<ThresholdConfig>(r#"{"threshold":6,"limit":5}"#).is_err());
+}
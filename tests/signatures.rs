@@ -1,8 +1,14 @@
 mod utils;
+use blsful::inner_types::Group;
 use blsful::{
-    AggregateSignature, Bls12381G1, Bls12381G1Impl, Bls12381G2, Bls12381G2Impl, BlsSignatureImpl,
-    MultiPublicKey, MultiSignature, PublicKey, SecretKey, Signature, SignatureSchemes,
+    AggregateSignature, Bls12381G1, Bls12381G1Impl, Bls12381G2, Bls12381G2Impl, BlsError,
+    BlsResult, BlsSignatureBasic, BlsSignatureCore, BlsSignatureImpl,
+    BlsSignatureMessageAugmentation, BlsSignaturePop, DealerProof, HashToPoint, KdfParams, KeyPair,
+    MultiPublicKey, MultiSignature, Pairing, PublicKey, PublicKeyShare, RotationProof, SecretKey,
+    SecretKeyShare, Signature, SignatureSchemes, SignatureSliceReader, ThresholdConfig,
+    VerificationCache,
 };
+use rand_core::SeedableRng;
 use rstest::*;
 use utils::*;
 
@@ -107,23 +113,23 @@ fn multisigs_work<C: BlsSignatureImpl>(#[case] _c: C) {
         .sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
         .unwrap();
 
-    let msig = MultiSignature::from_signatures(&[sig1, sig2, sig3]).unwrap();
+    let msig = MultiSignature::from_signatures([sig1, sig2, sig3]).unwrap();
     let mpk = MultiPublicKey::from([pk1, pk2, pk3].to_vec().as_slice());
     assert!(msig.verify(mpk, TEST_MSG).is_ok());
 
     let off_sig = sk1.sign(SignatureSchemes::Basic, TEST_MSG).unwrap();
-    let res = MultiSignature::from_signatures(&[sig1, sig2, sig3, off_sig]);
+    let res = MultiSignature::from_signatures([sig1, sig2, sig3, off_sig]);
     assert!(res.is_err());
 
     // miss a key
-    let mpk = MultiPublicKey::from_public_keys(&[pk1, pk2]);
+    let mpk = MultiPublicKey::from_public_keys([pk1, pk2]);
     assert!(msig.verify(mpk, TEST_MSG).is_err());
 
     let sk4 = SecretKey::<C>::new();
     let bad_sig = sk4
         .sign(SignatureSchemes::MessageAugmentation, TEST_MSG)
         .unwrap();
-    let res = MultiSignature::from_signatures(&[sig1, sig2, sig3, bad_sig]);
+    let res = MultiSignature::from_signatures([sig1, sig2, sig3, bad_sig]);
     assert!(res.is_err());
 }
 
@@ -143,7 +149,7 @@ fn aggegratesigs_work<C: BlsSignatureImpl>(#[case] _c: C) {
     let sig2 = sk2.sign(SignatureSchemes::Basic, TEST_MSG).unwrap();
     let sig3 = sk3.sign(SignatureSchemes::Basic, TEST_MSG).unwrap();
 
-    let asig = AggregateSignature::from_signatures(&[sig1, sig2, sig3]).unwrap();
+    let asig = AggregateSignature::from_signatures([sig1, sig2, sig3]).unwrap();
     assert!(asig
         .verify(&[(pk1, TEST_MSG), (pk2, TEST_MSG), (pk3, TEST_MSG)])
         .is_err());
@@ -151,7 +157,7 @@ fn aggegratesigs_work<C: BlsSignatureImpl>(#[case] _c: C) {
     let sig1 = sk1.sign(SignatureSchemes::Basic, b"sig1").unwrap();
     let sig2 = sk2.sign(SignatureSchemes::Basic, b"sig2").unwrap();
     let sig3 = sk3.sign(SignatureSchemes::Basic, b"sig3").unwrap();
-    let asig = AggregateSignature::from_signatures(&[sig1, sig2, sig3]).unwrap();
+    let asig = AggregateSignature::from_signatures([sig1, sig2, sig3]).unwrap();
     assert!(asig
         .verify(&[(pk1, b"sig1"), (pk2, b"sig2"), (pk3, b"sig3")])
         .is_ok());
@@ -166,8 +172,1304 @@ fn aggegratesigs_work<C: BlsSignatureImpl>(#[case] _c: C) {
         .sign(SignatureSchemes::MessageAugmentation, TEST_MSG)
         .unwrap();
 
-    let asig = AggregateSignature::from_signatures(&[sig1, sig2, sig3]).unwrap();
+    let asig = AggregateSignature::from_signatures([sig1, sig2, sig3]).unwrap();
     assert!(asig
         .verify(&[(pk1, TEST_MSG), (pk2, TEST_MSG), (pk3, TEST_MSG)])
         .is_ok());
 }
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn augment_message_works<C: BlsSignatureImpl>(#[case] _c: C) {
+    let sk = SecretKey::<C>::from_hash(b"augment_message_works");
+    let pk = sk.public_key();
+
+    let augmented = pk.augment_message(TEST_MSG);
+    let mut expected = Vec::from(&pk);
+    expected.extend_from_slice(TEST_MSG);
+    assert_eq!(augmented, expected);
+    assert_eq!(&augmented[augmented.len() - TEST_MSG.len()..], TEST_MSG);
+
+    // The augmented message is what's signed under the augmentation DST
+    let sig = sk
+        .sign(SignatureSchemes::MessageAugmentation, TEST_MSG)
+        .unwrap();
+    let raw = <C as BlsSignatureCore>::core_sign(
+        &sk.0,
+        &augmented,
+        <C as BlsSignatureMessageAugmentation>::DST,
+    )
+    .unwrap();
+    assert_eq!(*sig.as_raw_value(), raw);
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn aggregate_set_operations_work<C: BlsSignatureImpl>(#[case] _c: C) {
+    const MSGS: [&[u8]; 5] = [b"m0", b"m1", b"m2", b"m3", b"m4"];
+    let sks = (0..5).map(|_| SecretKey::<C>::new()).collect::<Vec<_>>();
+    let pks = sks.iter().map(|sk| sk.public_key()).collect::<Vec<_>>();
+    let sigs = sks
+        .iter()
+        .zip(MSGS)
+        .map(|(sk, m)| sk.sign(SignatureSchemes::ProofOfPossession, m).unwrap())
+        .collect::<Vec<_>>();
+
+    let aggregate = |bits: &[bool]| {
+        let selected = sigs
+            .iter()
+            .zip(bits)
+            .filter(|(_, b)| **b)
+            .map(|(s, _)| *s)
+            .collect::<Vec<_>>();
+        AggregateSignature::from_signatures(selected).unwrap()
+    };
+    let items = |bits: &[bool]| {
+        pks.iter()
+            .zip(MSGS)
+            .zip(bits)
+            .filter(|(_, b)| **b)
+            .map(|((pk, m), _)| (*pk, m))
+            .collect::<Vec<_>>()
+    };
+
+    let a_bits = [true, true, true, true, false];
+    let b_bits = [false, false, true, true, true];
+    let i_bits = [false, false, true, true, false];
+    let a = aggregate(&a_bits);
+    let b = aggregate(&b_bits);
+    let i = aggregate(&i_bits);
+
+    let (sym, sym_bits) = a.symmetric_difference(&a_bits, &b, &b_bits, &i).unwrap();
+    assert_eq!(sym_bits, vec![true, true, false, false, true]);
+    assert!(sym.verify(&items(&sym_bits)).is_ok());
+    assert!(sym.verify(&items(&a_bits)).is_err());
+
+    let (diff, diff_bits) = a.difference(&a_bits, &i, &i_bits).unwrap();
+    assert_eq!(diff_bits, vec![true, true, false, false, false]);
+    assert!(diff.verify(&items(&diff_bits)).is_ok());
+
+    // b is not a subset of a
+    assert!(a.difference(&a_bits, &b, &b_bits).is_err());
+    // mismatched bitfield lengths
+    assert!(a.difference(&a_bits, &i, &i_bits[1..]).is_err());
+    // mismatched schemes
+    let basic = AggregateSignature::from_signatures([
+        sks[0].sign(SignatureSchemes::Basic, MSGS[0]).unwrap(),
+        sks[1].sign(SignatureSchemes::Basic, MSGS[1]).unwrap(),
+    ])
+    .unwrap();
+    assert!(a.subtract(&basic).is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_segments_matches_concatenation<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let (header, body) = TEST_MSG.split_at(4);
+    for scheme in [
+        SignatureSchemes::Basic,
+        SignatureSchemes::MessageAugmentation,
+        SignatureSchemes::ProofOfPossession,
+    ] {
+        let sig = sk.sign(scheme, TEST_MSG).unwrap();
+        let seg_sig = sk.sign_segments(scheme, &[header, body]).unwrap();
+        assert_eq!(sig, seg_sig);
+        assert!(seg_sig.verify_segments(&pk, &[header, body]).is_ok());
+        assert!(seg_sig.verify(&pk, TEST_MSG).is_ok());
+        assert!(seg_sig.verify_segments(&pk, &[body, header]).is_err());
+
+        let empty = sk.sign(scheme, &[]).unwrap();
+        assert_eq!(empty, sk.sign_segments(scheme, &[]).unwrap());
+        assert!(empty.verify_segments(&pk, &[]).is_ok());
+    }
+}
+
+/// Reshare with every holder of `old_shares` dealing only their own share
+/// and every new member combining only the dealings sent to them
+fn reshare_committee<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    old_config: ThresholdConfig,
+    old_shares: &[SecretKeyShare<C>],
+    new_config: ThresholdConfig,
+) -> BlsResult<(Vec<SecretKeyShare<C>>, RotationProof<C>)> {
+    let dealings = old_shares
+        .iter()
+        .map(|s| Ok((s.public_key()?, s.reshare(new_config)?)))
+        .collect::<BlsResult<Vec<_>>>()?;
+    let mut new_shares = Vec::with_capacity(new_config.limit());
+    let mut proofs = Vec::with_capacity(new_config.limit());
+    for j in 0..new_config.limit() {
+        let received = dealings
+            .iter()
+            .map(|(dealer, (shares, proof))| (*dealer, shares[j].clone(), proof.clone()))
+            .collect::<Vec<_>>();
+        let (share, proof) = SecretKeyShare::combine_reshares(old_config, &received)?;
+        new_shares.push(share);
+        proofs.push(proof);
+    }
+    assert!(proofs.iter().all(|p| *p == proofs[0]));
+    Ok((new_shares, proofs.swap_remove(0)))
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn rotation_proof_works<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let old_config = ThresholdConfig::new(3, 5).unwrap();
+    let new_config = ThresholdConfig::new(4, 7).unwrap();
+    let old_shares = sk.split(3, 5).unwrap();
+
+    let (new_shares, proof) = reshare_committee(old_config, &old_shares[1..4], new_config).unwrap();
+    let new_pk_shares = new_shares
+        .iter()
+        .map(|s| s.public_key().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(new_shares.len(), 7);
+    assert_eq!(proof.threshold(), 4);
+    assert_eq!(proof.group_public_key().unwrap(), pk);
+    assert!(proof.verify_rotation(&pk, &new_pk_shares).is_ok());
+    assert!(proof.verify_rotation(&pk, &new_pk_shares[3..]).is_ok());
+    for share in &new_pk_shares {
+        assert!(proof.verify_share(share).is_ok());
+    }
+    assert_eq!(SecretKey::combine(&new_shares[2..6]).unwrap(), sk);
+
+    let sig_shares = new_shares[..4]
+        .iter()
+        .map(|s| {
+            s.sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let sig = Signature::from_shares(&sig_shares).unwrap();
+    assert!(sig.verify(&pk, TEST_MSG).is_ok());
+
+    // Fewer than threshold shares, or one share repeated, prove nothing
+    assert!(matches!(
+        proof.verify_rotation(&pk, &new_pk_shares[..3]),
+        Err(BlsError::InsufficientShares {
+            threshold: 4,
+            received: 3
+        })
+    ));
+    assert!(proof.verify_rotation(&pk, &[new_pk_shares[0]; 4]).is_err());
+
+    // Commitments that start with the group key but weren't dealt are rejected
+    let mut forged = proof.clone();
+    forged.0.commitments[1] = SecretKey::<C>::new().public_key();
+    assert!(forged.verify_rotation(&pk, &new_pk_shares).is_err());
+
+    // A rotation to a different key doesn't link to the old key
+    let other_sk = SecretKey::<C>::new();
+    let (other_shares, other_proof) =
+        reshare_committee(old_config, &other_sk.split(3, 5).unwrap(), old_config).unwrap();
+    let other_pk_shares = other_shares
+        .iter()
+        .map(|s| s.public_key().unwrap())
+        .collect::<Vec<_>>();
+    assert!(other_proof
+        .verify_rotation(&other_sk.public_key(), &other_pk_shares)
+        .is_ok());
+    assert!(other_proof.verify_rotation(&pk, &other_pk_shares).is_err());
+    assert!(proof.verify_rotation(&pk, &other_pk_shares).is_err());
+    assert!(proof.verify_share(&other_pk_shares[0]).is_err());
+
+    // Too few old holders can't reshare
+    assert!(matches!(
+        reshare_committee(old_config, &old_shares[..2], new_config),
+        Err(BlsError::InsufficientShares {
+            threshold: 3,
+            received: 2
+        })
+    ));
+
+    // A holder must deal their own share
+    let mut received = old_shares[..3]
+        .iter()
+        .map(|s| {
+            let (shares, proof) = s.reshare(new_config).unwrap();
+            (s.public_key().unwrap(), shares[0].clone(), proof)
+        })
+        .collect::<Vec<_>>();
+    received[1].0 = old_shares[3].public_key().unwrap();
+    assert!(matches!(
+        SecretKeyShare::combine_reshares(old_config, &received),
+        Err(BlsError::InvalidProof)
+    ));
+    received[1].0 = received[0].0;
+    assert!(SecretKeyShare::combine_reshares(old_config, &received).is_err());
+
+    let bytes = Vec::from(&proof);
+    assert_eq!(RotationProof::<C>::try_from(bytes).unwrap(), proof);
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_returning_hash_works<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    for scheme in [
+        SignatureSchemes::Basic,
+        SignatureSchemes::MessageAugmentation,
+        SignatureSchemes::ProofOfPossession,
+    ] {
+        let (sig, hash) = sk.sign_returning_hash(scheme, TEST_MSG).unwrap();
+        assert_eq!(sig, sk.sign(scheme, TEST_MSG).unwrap());
+        assert!(sig.verify_point(&pk, hash).is_ok());
+        assert!(sig.verify(&pk, TEST_MSG).is_ok());
+
+        let (_, bad_hash) = sk.sign_returning_hash(scheme, BAD_MSG).unwrap();
+        assert!(sig.verify_point(&pk, bad_hash).is_err());
+    }
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn signature_from_extra_shares<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    const THRESHOLD: usize = 3;
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let shares = sk.split(THRESHOLD, THRESHOLD + 2).unwrap();
+    for scheme in [SignatureSchemes::Basic, SignatureSchemes::ProofOfPossession] {
+        let sig_shares = shares
+            .iter()
+            .map(|s| s.sign(scheme, TEST_MSG).unwrap())
+            .collect::<Vec<_>>();
+
+        let exact = Signature::from_shares(&sig_shares[..THRESHOLD]).unwrap();
+        let extra = Signature::from_shares(&sig_shares).unwrap();
+        assert_eq!(exact, extra);
+        assert_eq!(extra, sk.sign(scheme, TEST_MSG).unwrap());
+        assert!(extra.verify(&pk, TEST_MSG).is_ok());
+
+        let subset = Signature::from_shares(&sig_shares[1..]).unwrap();
+        assert_eq!(subset, extra);
+    }
+    assert!(Signature::<C>::from_shares(&[]).is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn signature_from_shares_verified<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let shares = sk.split(3, 5).unwrap();
+    let sig_shares = shares
+        .iter()
+        .map(|s| {
+            s.sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let sig = Signature::from_shares_verified(&sig_shares, &pk, TEST_MSG).unwrap();
+    assert_eq!(sig, Signature::from_shares(&sig_shares[..3]).unwrap());
+    assert!(Signature::from_shares_verified(&sig_shares, &pk, b"wrong message").is_err());
+    let other_pk = SecretKey::<C>::new().public_key();
+    assert!(Signature::from_shares_verified(&sig_shares, &other_pk, TEST_MSG).is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn verification_cache_hits<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let sig = sk
+        .sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
+        .unwrap();
+    let cache = VerificationCache::<C>::new(8).unwrap();
+    assert!(cache.is_empty());
+
+    assert!(cache.verify(&pk, &sig, TEST_MSG).is_ok());
+    assert!(cache.verify(&pk, &sig, TEST_MSG).is_ok());
+    assert_eq!(cache.len(), 1);
+
+    assert!(cache.verify(&pk, &sig, b"wrong message").is_err());
+    assert!(cache.verify(&pk, &sig, b"wrong message").is_err());
+    assert_eq!(cache.len(), 2);
+
+    std::thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| assert!(cache.verify(&pk, &sig, TEST_MSG).is_ok()));
+        }
+    });
+    assert_eq!(cache.len(), 2);
+
+    cache.clear();
+    assert!(cache.is_empty());
+    assert!(VerificationCache::<C>::new(0).is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn aggregate_contributions_sort_canonically<C: BlsSignatureImpl>(#[case] _c: C) {
+    let contributions = (0..5)
+        .map(|_| {
+            let sk = SecretKey::<C>::new();
+            (
+                sk.public_key(),
+                sk.sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
+                    .unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut node_a = contributions.clone();
+    let mut node_b = contributions.clone();
+    node_b.reverse();
+    node_b.rotate_left(2);
+    AggregateSignature::sort_contributions(&mut node_a);
+    AggregateSignature::sort_contributions(&mut node_b);
+
+    assert!(node_a
+        .windows(2)
+        .all(|w| Vec::from(&w[0].0) <= Vec::from(&w[1].0)));
+    #[cfg(feature = "serde")]
+    assert_eq!(
+        serde_json::to_vec(&node_a).unwrap(),
+        serde_json::to_vec(&node_b).unwrap()
+    );
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn public_key_share_verify_single<C: BlsSignatureImpl>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let (shares, proof) = sk
+        .split_with_proof(ThresholdConfig::new(3, 5).unwrap(), MockRng::default())
+        .unwrap();
+
+    // A participant only knows their own share and the dealer's commitments
+    let mine = shares[2].public_key().unwrap();
+    assert_eq!(mine.verify_single(&proof.commitments).unwrap_u8(), 1u8);
+
+    let other = SecretKey::<C>::new().split(3, 5).unwrap()[2]
+        .public_key()
+        .unwrap();
+    assert_eq!(other.verify_single(&proof.commitments).unwrap_u8(), 0u8);
+    assert_eq!(mine.verify_single(&proof.commitments[..2]).unwrap_u8(), 0u8);
+    assert_eq!(mine.verify_single(&[]).unwrap_u8(), 0u8);
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn additive_shares_upgrade_to_shamir<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    let config = ThresholdConfig::new(3, 5).unwrap();
+    let additive = (0..4).map(|_| SecretKey::<C>::new()).collect::<Vec<_>>();
+    let group_pub = PublicKey::<C>(
+        additive
+            .iter()
+            .fold(<C as Pairing>::PublicKey::identity(), |acc, sk| {
+                acc + sk.public_key().0
+            }),
+    );
+
+    // Each holder deals only their own additive share
+    let dealings = additive
+        .iter()
+        .map(|sk| sk.deal_additive(config).unwrap())
+        .collect::<Vec<_>>();
+
+    // Each recipient sums only the dealings sent to them
+    let (shares, proofs): (Vec<_>, Vec<_>) = (0..5)
+        .map(|j| {
+            let received = dealings
+                .iter()
+                .map(|(shares, proof)| (shares[j].clone(), proof.clone()))
+                .collect::<Vec<_>>();
+            SecretKeyShare::combine_dealings(&received).unwrap()
+        })
+        .unzip();
+    assert!(proofs.iter().all(|p| *p == proofs[0]));
+    let proof = &proofs[0];
+    assert_eq!(proof.group_public_key().unwrap(), group_pub);
+    assert!(proof.verify(&group_pub).is_ok());
+    for share in &shares {
+        assert!(proof.verify_feldman(share).is_ok());
+    }
+    assert_eq!(
+        SecretKey::combine(&shares[..3]).unwrap().public_key(),
+        group_pub
+    );
+    assert_eq!(
+        SecretKey::combine(&shares[2..]).unwrap().public_key(),
+        group_pub
+    );
+
+    // A tampered dealing is caught by the recipient
+    let mut received = dealings
+        .iter()
+        .map(|(shares, proof)| (shares[0].clone(), proof.clone()))
+        .collect::<Vec<_>>();
+    received[1].0 = dealings[1].0[1].clone();
+    assert!(SecretKeyShare::combine_dealings(&received).is_err());
+    received[1].0 = dealings[2].0[0].clone();
+    assert!(matches!(
+        SecretKeyShare::combine_dealings(&received),
+        Err(BlsError::InvalidProof)
+    ));
+
+    assert!(SecretKeyShare::<C>::combine_dealings(&[]).is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn verify_rejects_identity<C: BlsSignatureImpl>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let identity_pk = PublicKey::<C>(<C as Pairing>::PublicKey::identity());
+    for scheme in [
+        SignatureSchemes::Basic,
+        SignatureSchemes::MessageAugmentation,
+        SignatureSchemes::ProofOfPossession,
+    ] {
+        let sig = sk.sign(scheme, TEST_MSG).unwrap();
+        let identity_sig = match sig {
+            Signature::Basic(_) => Signature::Basic(<C as Pairing>::Signature::identity()),
+            Signature::MessageAugmentation(_) => {
+                Signature::MessageAugmentation(<C as Pairing>::Signature::identity())
+            }
+            Signature::ProofOfPossession(_) => {
+                Signature::ProofOfPossession(<C as Pairing>::Signature::identity())
+            }
+        };
+
+        assert!(matches!(
+            identity_sig.verify(&pk, TEST_MSG),
+            Err(BlsError::InvalidSignature)
+        ));
+        assert!(matches!(
+            sig.verify(&identity_pk, TEST_MSG),
+            Err(BlsError::InvalidPublicKey)
+        ));
+        assert!(matches!(
+            identity_sig.verify(&identity_pk, TEST_MSG),
+            Err(BlsError::InvalidSignature)
+        ));
+    }
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn public_key_share_from_secret_key_share<
+    C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug,
+>(
+    #[case] _c: C,
+) {
+    let sk = SecretKey::<C>::new();
+    let (shares, proof) = sk
+        .split_with_proof(ThresholdConfig::new(3, 5).unwrap(), MockRng::default())
+        .unwrap();
+    let public_shares = shares.iter().map(PublicKeyShare::from).collect::<Vec<_>>();
+
+    for (share, public_share) in shares.iter().zip(&public_shares) {
+        assert_eq!(*public_share, share.public_key().unwrap());
+        assert!(proof.verify_share(public_share).is_ok());
+
+        let sig = share.sign(SignatureSchemes::Basic, TEST_MSG).unwrap();
+        assert!(public_share.verify(&sig, TEST_MSG).is_ok());
+    }
+    assert_eq!(
+        PublicKey::from_shares(&public_shares[1..4]).unwrap(),
+        sk.public_key()
+    );
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn secret_key_from_passphrase<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    const PASSPHRASE: &[u8] = b"correct horse battery staple";
+    const SALT: &[u8] = b"mnemonic";
+
+    let params = KdfParams::default();
+    assert_eq!(params, KdfParams::new(KdfParams::BIP39_ITERATIONS).unwrap());
+    let sk = SecretKey::<C>::from_passphrase(PASSPHRASE, SALT, params).unwrap();
+    assert_eq!(
+        sk,
+        SecretKey::<C>::from_passphrase(PASSPHRASE, SALT, params).unwrap()
+    );
+
+    let stronger = KdfParams::new(4096).unwrap();
+    assert_ne!(
+        sk,
+        SecretKey::<C>::from_passphrase(PASSPHRASE, SALT, stronger).unwrap()
+    );
+    assert_ne!(
+        sk,
+        SecretKey::<C>::from_passphrase(PASSPHRASE, b"other", params).unwrap()
+    );
+    assert!(KdfParams::new(1).is_err());
+    assert_eq!(stronger.iterations(), 4096);
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn aggregate_verify_rejects_empty<C: BlsSignatureImpl>(#[case] _c: C) {
+    let sks = [SecretKey::<C>::new(), SecretKey::<C>::new()];
+    let sigs = sks
+        .iter()
+        .map(|sk| sk.sign(SignatureSchemes::Basic, TEST_MSG).unwrap())
+        .collect::<Vec<_>>();
+    let aggregate = AggregateSignature::from_signatures(&sigs).unwrap();
+    let empty: &[(PublicKey<C>, &[u8])] = &[];
+    assert!(matches!(
+        aggregate.verify(empty),
+        Err(BlsError::EmptyAggregate)
+    ));
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn share_correctness_proof<C: BlsSignatureImpl>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let shares = sk.split_with_rng(2, 3, rand_core::OsRng).unwrap();
+    let pks = shares
+        .iter()
+        .map(|s| s.public_key().unwrap())
+        .collect::<Vec<_>>();
+    let sig1 = shares[0].sign(SignatureSchemes::Basic, TEST_MSG).unwrap();
+    let sig2 = shares[1]
+        .sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
+        .unwrap();
+
+    // Honest signers
+    let proof1 = sig1.prove_correctness(&shares[0], TEST_MSG).unwrap();
+    assert!(sig1
+        .verify_share_correctness(&pks[0], TEST_MSG, &proof1)
+        .is_ok());
+    let proof2 = sig2.prove_correctness(&shares[1], TEST_MSG).unwrap();
+    assert!(sig2
+        .verify_share_correctness(&pks[1], TEST_MSG, &proof2)
+        .is_ok());
+
+    // A proof can't be created for a share the key didn't produce
+    assert!(sig1.prove_correctness(&shares[1], TEST_MSG).is_err());
+    assert!(sig1
+        .prove_correctness(&shares[0], b"other message")
+        .is_err());
+
+    // A share computed over a different message doesn't check out
+    let bad = shares[0]
+        .sign(SignatureSchemes::Basic, b"other message")
+        .unwrap();
+    let bad_proof = bad.prove_correctness(&shares[0], b"other message").unwrap();
+    assert!(matches!(
+        bad.verify_share_correctness(&pks[0], TEST_MSG, &bad_proof),
+        Err(BlsError::InvalidProof)
+    ));
+
+    // Proofs are bound to the share and the signer
+    assert!(matches!(
+        sig1.verify_share_correctness(&pks[0], TEST_MSG, &proof2),
+        Err(BlsError::InvalidProof)
+    ));
+    assert!(sig1
+        .verify_share_correctness(&pks[1], TEST_MSG, &proof1)
+        .is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_structured_binds_domain_and_struct<
+    C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug,
+>(
+    #[case] _c: C,
+) {
+    let sk = SecretKey::<C>::from_hash(b"sign_structured_binds_domain_and_struct");
+    let pk = sk.public_key();
+    let domain = [1u8; 32];
+    let struct_hash = [2u8; 32];
+
+    for scheme in [
+        SignatureSchemes::Basic,
+        SignatureSchemes::MessageAugmentation,
+        SignatureSchemes::ProofOfPossession,
+    ] {
+        let sig = sk.sign_structured(scheme, &domain, &struct_hash).unwrap();
+        assert!(sig.verify_structured(&pk, &domain, &struct_hash).is_ok());
+
+        let other_domain = sk
+            .sign_structured(scheme, &[3u8; 32], &struct_hash)
+            .unwrap();
+        let other_struct = sk.sign_structured(scheme, &domain, &[3u8; 32]).unwrap();
+        let swapped = sk.sign_structured(scheme, &struct_hash, &domain).unwrap();
+        assert_ne!(sig, other_domain);
+        assert_ne!(sig, other_struct);
+        assert_ne!(sig, swapped);
+
+        assert!(sig
+            .verify_structured(&pk, &[3u8; 32], &struct_hash)
+            .is_err());
+        assert!(sig.verify_structured(&pk, &domain, &[3u8; 32]).is_err());
+        assert!(sig.verify(&pk, [domain, struct_hash].concat()).is_err());
+    }
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn multi_scheme_aggregate_verify<C: BlsSignatureImpl>(#[case] _c: C) {
+    let keys = [
+        SecretKey::<C>::new(),
+        SecretKey::<C>::new(),
+        SecretKey::<C>::new(),
+    ];
+    let msgs: [&[u8]; 3] = [b"basic one", b"basic two", TEST_MSG];
+    let schemes = [
+        SignatureSchemes::Basic,
+        SignatureSchemes::Basic,
+        SignatureSchemes::ProofOfPossession,
+    ];
+    let sigs = keys
+        .iter()
+        .zip(msgs)
+        .zip(schemes)
+        .map(|((sk, msg), scheme)| sk.sign(scheme, msg).unwrap())
+        .collect::<Vec<_>>();
+    // Mixing schemes isn't allowed for a single scheme aggregate
+    assert!(AggregateSignature::from_signatures(&sigs).is_err());
+    let sig = <C as BlsSignatureCore>::aggregate_signatures(sigs.iter().map(|s| *s.as_raw_value()));
+
+    let mut items = keys
+        .iter()
+        .zip(msgs)
+        .zip(schemes)
+        .map(|((sk, msg), scheme)| (sk.public_key(), msg, scheme))
+        .collect::<Vec<_>>();
+    assert!(AggregateSignature::verify_multi_scheme_aggregate(&items, &sig).is_ok());
+
+    // Every message must be hashed under the scheme that signed it
+    items[2].2 = SignatureSchemes::Basic;
+    assert!(matches!(
+        AggregateSignature::verify_multi_scheme_aggregate(&items, &sig),
+        Err(BlsError::InvalidSignature)
+    ));
+    items[2].2 = SignatureSchemes::ProofOfPossession;
+    items[0].1 = b"other message";
+    assert!(AggregateSignature::verify_multi_scheme_aggregate(&items, &sig).is_err());
+
+    // Basic scheme messages must be distinct
+    items[0].1 = b"basic two";
+    assert!(matches!(
+        AggregateSignature::verify_multi_scheme_aggregate(&items, &sig),
+        Err(BlsError::InvalidInputs(_))
+    ));
+
+    assert!(matches!(
+        AggregateSignature::<C>::verify_multi_scheme_aggregate(&[], &sig),
+        Err(BlsError::EmptyAggregate)
+    ));
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn public_key_share_interpolate_at<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    use std::num::NonZeroUsize;
+
+    let (shares, proof) = SecretKey::<C>::new()
+        .split_with_proof(ThresholdConfig::new(3, 5).unwrap(), MockRng::default())
+        .unwrap();
+    let pk_shares = shares
+        .iter()
+        .map(|s| s.public_key().unwrap())
+        .collect::<Vec<_>>();
+
+    // Existing members' shares are reproduced from any threshold of others
+    for x in 1..=5 {
+        let share =
+            PublicKeyShare::interpolate_at(&pk_shares[2..], NonZeroUsize::new(x).unwrap()).unwrap();
+        assert_eq!(share, pk_shares[x - 1]);
+    }
+
+    // A new member's share lies on the dealer's polynomial
+    let share =
+        PublicKeyShare::interpolate_at(&pk_shares[..3], NonZeroUsize::new(9).unwrap()).unwrap();
+    assert!(bool::from(share.verify_single(&proof.commitments)));
+    assert!(proof.verify_share(&share).is_ok());
+
+    // Too few shares give a point off the polynomial
+    let share =
+        PublicKeyShare::interpolate_at(&pk_shares[..2], NonZeroUsize::new(9).unwrap()).unwrap();
+    assert!(!bool::from(share.verify_single(&proof.commitments)));
+
+    assert!(PublicKeyShare::<C>::interpolate_at(&[], NonZeroUsize::new(1).unwrap()).is_err());
+    assert!(PublicKeyShare::interpolate_at(
+        &[pk_shares[0], pk_shares[0]],
+        NonZeroUsize::new(9).unwrap()
+    )
+    .is_err());
+}
+
+/// Hash to curve streams the message into the hash and its length counter is
+/// the fixed output length, so message size can't overflow it. Both backends
+/// produce these signatures.
+#[rstest]
+#[case::g1(Bls12381G1Impl, [
+    "00ac97cad1d1736a1ae4b3c69bee4ff059b0de053aa10ff86352f3669cca6ea519cc748626746ded64f09cfc288c09e46c",
+    "0198de3939e859b5be6c509f1fe40a52aa35aaeeb0aceff609e056c3db8a1895066fe793382050ed31a58f5db8a55c9277",
+    "0295ca61b44ab4143aab01d0e6c04be5c4b99d88be4f4b754b2a6a27974f3387dab86c3aeff2b898427ba5045d50f6daee",
+])]
+#[case::g2(Bls12381G2Impl, [
+    "00ab7c5a82081adfe41d00dace43634681292a01e08e1a25b1fa406a0b9abc09676e29ab81d6664b6c483c65080e45e759118ba593073828f7a5d162109ded4be8c93e181f319a5ebb7571eb97b8d564774cccef823447fa871ad6d3e01aef8b0a",
+    "018e82cda6c2915da9f657e5d79111f113ff74af5c58b1701b18370e29d4e3dd450004cd972d6be4b5309e080e0d92b6e317158322c92f66d7ba240cc98060b82a8946e7bfa002b35ce91d84fb6a23b505cdff696ed310a9d7eb49ceb45b7b7525",
+    "02abcbb81c6c7f8243579eea57e0a6efdda91b894c52be03a9cc12c75ba509cca24ced8d7372532a0c8c26648eae4410c719889af5947a291b9a9f92ca800d4c5855f9914adb459c1a64ea49d3905a9f583ecd33e31afd37eba581e00d152f29ac",
+])]
+#[ignore = "signs a 257 MiB message, run with --ignored"]
+fn sign_large_message<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+    #[case] expected: [&str; 3],
+) {
+    const LEN: usize = (256 << 20) + (1 << 20);
+    let msg = (0..LEN).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+    let sk = SecretKey::<C>::from_hash(b"sign_large_message");
+    let pk = sk.public_key();
+
+    for (scheme, expected) in [
+        SignatureSchemes::Basic,
+        SignatureSchemes::MessageAugmentation,
+        SignatureSchemes::ProofOfPossession,
+    ]
+    .into_iter()
+    .zip(expected)
+    {
+        let sig = sk.sign(scheme, &msg).unwrap();
+        assert_eq!(hex::encode(Vec::from(&sig)), expected);
+        assert!(sig.verify(&pk, &msg).is_ok());
+        assert!(sig.verify(&pk, &msg[..LEN - 1]).is_err());
+    }
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn key_pair_rotate<C: BlsSignatureImpl + PartialEq + Eq + Clone + std::fmt::Debug>(#[case] _c: C) {
+    let old = KeyPair::<C>::new();
+    let (new, certificate) = old.rotate(MockRng::default());
+    assert_ne!(old.public_key(), new.public_key());
+    assert_eq!(*new.public_key(), new.secret_key().public_key());
+    assert!(KeyPair::verify_rotation(old.public_key(), new.public_key(), &certificate).is_ok());
+
+    // The certificate doesn't vouch for any other key
+    let other = KeyPair::<C>::new();
+    assert!(KeyPair::verify_rotation(old.public_key(), other.public_key(), &certificate).is_err());
+    assert!(KeyPair::verify_rotation(other.public_key(), new.public_key(), &certificate).is_err());
+    let (_, other_certificate) = old.rotate(MockRng::default());
+    assert!(
+        KeyPair::verify_rotation(old.public_key(), new.public_key(), &other_certificate).is_ok()
+    );
+    let (other_new, other_certificate) = old.rotate(rand_core::OsRng);
+    assert!(
+        KeyPair::verify_rotation(old.public_key(), new.public_key(), &other_certificate).is_err()
+    );
+    assert!(
+        KeyPair::verify_rotation(old.public_key(), other_new.public_key(), &other_certificate)
+            .is_ok()
+    );
+
+    // A proof of possession signature over the bare key bytes isn't a certificate
+    let raw = old
+        .secret_key()
+        .sign(
+            SignatureSchemes::ProofOfPossession,
+            &Vec::from(new.public_key()),
+        )
+        .unwrap();
+    assert!(KeyPair::verify_rotation(old.public_key(), new.public_key(), &raw).is_err());
+    let mut msg = KeyPair::<C>::ROTATION_PREFIX.to_vec();
+    msg.extend_from_slice(&Vec::from(new.public_key()));
+    assert!(certificate.verify(old.public_key(), &msg).is_ok());
+
+    let sk = SecretKey::<C>::new();
+    assert_eq!(
+        *KeyPair::try_from(sk.clone()).unwrap().public_key(),
+        sk.public_key()
+    );
+    assert!(matches!(
+        KeyPair::<C>::try_from(SecretKey(Default::default())),
+        Err(BlsError::InvalidSecretKey)
+    ));
+}
+
+/// Mirrors `FastAggregateVerify` from the Ethereum consensus spec where any
+/// input that fails to decode is also a verification failure
+fn eth2_fast_aggregate_verify(pks: &[&str], msg: &[u8], sig: &str) -> bool {
+    let pks = pks
+        .iter()
+        .map(|pk| PublicKey::<Bls12381G2Impl>::try_from(hex::decode(pk).unwrap()))
+        .collect::<Result<Vec<_>, _>>();
+    let sig = Signature::<Bls12381G2Impl>::from_tagged_bytes(
+        &[
+            &[SignatureSchemes::ProofOfPossession as u8][..],
+            &hex::decode(sig).unwrap(),
+        ]
+        .concat(),
+    );
+    match (pks, sig) {
+        (Ok(pks), Ok((_, sig))) => sig.fast_aggregate_verify(&pks, msg).is_ok(),
+        _ => false,
+    }
+}
+
+/// Cases from the Ethereum consensus `fast_aggregate_verify` BLS test vectors
+#[test]
+fn fast_aggregate_verify_eth2_vectors() {
+    const PKS: [&str; 3] = [
+        "a491d1b0ecd9bb917989f0e74f0dea0422eac4a873e5e2644f368dffb9a6e20fd6e10c1b77654d067c0618f6e5a7f79a",
+        "b301803f8b5ac4a1133581fc676dfedc60d891dd5fa99028805e5ea5b08d3491af75d0707adab3b70c6a6a580217bf81",
+        "b53d21a4cfd562c469cc81514d4ce5a6b577d8403d32a394dc265dd190b47fa9f829fdd7963afdf972e5e77854051f6f",
+    ];
+    const INFINITY_PK: &str = "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+    const SIG_1: &str = "b6ed936746e01f8ecf281f020953fbf1f01debd5657c4a383940b020b26507f6076334f91e2366c96e9ab279fb5158090352ea1c5b0c9274504f4f0e7053af24802e51e4568d164fe986834f41e55c8e850ce1f98458c0cfc9ab380b55285a55";
+    const SIG_2: &str = "912c3615f69575407db9392eb21fee18fff797eeb2fbe1816366ca2a08ae574d8824dbfafb4c9eaa1cf61b63c6f9b69911f269b664c42947dd1b53ef1081926c1e82bb2a465f927124b08391a5249036146d6f3f1e17ff5f162f779746d830d1";
+    const SIG_3: &str = "9712c3edd73a209c742b8250759db12549b3eaf43b5ca61376d9f30e2747dbcf842d8b2ac0901d2a093713e20284a7670fcf6954e9ab93de991bb9b313e664785a075fc285806fa5224c82bde146561b446ccfc706a64b8579513cfc4ff1d930";
+    const SIG_3_TAMPERED: &str = "9712c3edd73a209c742b8250759db12549b3eaf43b5ca61376d9f30e2747dbcf842d8b2ac0901d2a093713e20284a7670fcf6954e9ab93de991bb9b313e664785a075fc285806fa5224c82bde146561b446ccfc706a64b8579513cfcffffffff";
+    const INFINITY_SIG: &str = "c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+    const ZERO_SIG: &str = "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000";
+
+    // valid
+    assert!(eth2_fast_aggregate_verify(&PKS[..1], &[0x00; 32], SIG_1));
+    assert!(eth2_fast_aggregate_verify(&PKS[..2], &[0x56; 32], SIG_2));
+    assert!(eth2_fast_aggregate_verify(&PKS, &[0xab; 32], SIG_3));
+
+    // extra pubkey
+    assert!(!eth2_fast_aggregate_verify(&PKS[..2], &[0x00; 32], SIG_1));
+    assert!(!eth2_fast_aggregate_verify(&PKS, &[0x56; 32], SIG_2));
+
+    // tampered signature
+    assert!(!eth2_fast_aggregate_verify(
+        &PKS,
+        &[0xab; 32],
+        SIG_3_TAMPERED
+    ));
+
+    // wrong message
+    assert!(!eth2_fast_aggregate_verify(&PKS, &[0x56; 32], SIG_3));
+
+    // no pubkeys
+    assert!(!eth2_fast_aggregate_verify(&[], &[0xab; 32], INFINITY_SIG));
+    assert!(!eth2_fast_aggregate_verify(&[], &[0xab; 32], ZERO_SIG));
+
+    // infinity pubkey
+    let with_infinity = [PKS[0], PKS[1], PKS[2], INFINITY_PK];
+    assert!(!eth2_fast_aggregate_verify(
+        &with_infinity,
+        &[0xab; 32],
+        SIG_3
+    ));
+    assert!(!eth2_fast_aggregate_verify(
+        &[INFINITY_PK],
+        &[0xab; 32],
+        INFINITY_SIG
+    ));
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn fast_aggregate_verify<C: BlsSignatureImpl>(#[case] _c: C) {
+    let sks = [SecretKey::<C>::new(), SecretKey::<C>::new()];
+    let pks = sks.iter().map(SecretKey::public_key).collect::<Vec<_>>();
+    let sigs = sks
+        .iter()
+        .map(|sk| {
+            sk.sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let sig = Signature::ProofOfPossession(*sigs[0].as_raw_value() + sigs[1].as_raw_value());
+    assert!(sig.fast_aggregate_verify(&pks, TEST_MSG).is_ok());
+    assert!(matches!(
+        sig.fast_aggregate_verify(&[], TEST_MSG),
+        Err(BlsError::EmptyAggregate)
+    ));
+
+    let sig = Signature::Basic(*sig.as_raw_value());
+    assert!(matches!(
+        sig.fast_aggregate_verify(&pks, TEST_MSG),
+        Err(BlsError::InvalidSignatureScheme)
+    ));
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn secret_key_share_is_consistent_with<C: BlsSignatureImpl>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let shares = sk.split(3, 5).unwrap();
+    let pk_shares = shares
+        .iter()
+        .map(|s| s.public_key().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        shares[0]
+            .is_consistent_with(&pk, &pk_shares[1..3])
+            .unwrap_u8(),
+        1
+    );
+    // The full published set including this participant's own share
+    assert_eq!(shares[0].is_consistent_with(&pk, &pk_shares).unwrap_u8(), 1);
+    assert_eq!(
+        shares[4]
+            .is_consistent_with(&pk, &pk_shares[..2])
+            .unwrap_u8(),
+        1
+    );
+    assert_eq!(
+        shares[0]
+            .is_consistent_with(&SecretKey::<C>::new().public_key(), &pk_shares[1..3])
+            .unwrap_u8(),
+        0
+    );
+
+    // Same identifier but from a different dealing
+    let tampered = SecretKey::<C>::new().split(3, 5).unwrap().remove(0);
+    assert_eq!(
+        tampered
+            .is_consistent_with(&pk, &pk_shares[1..3])
+            .unwrap_u8(),
+        0
+    );
+    assert_eq!(
+        tampered
+            .is_consistent_with(&pk, &pk_shares[1..])
+            .unwrap_u8(),
+        0
+    );
+    assert_eq!(tampered.is_consistent_with(&pk, &pk_shares).unwrap_u8(), 0);
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn signature_slice_reader<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(#[case] _c: C) {
+    let sigs = (0..100u32)
+        .map(|i| {
+            SecretKey::<C>::from_hash(i.to_be_bytes())
+                .sign(SignatureSchemes::ProofOfPossession, TEST_MSG)
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+    let buffer = sigs
+        .iter()
+        .flat_map(|s| s.to_tagged_bytes()[1..].to_vec())
+        .collect::<Vec<_>>();
+    assert_eq!(buffer.len(), 100 * SignatureSliceReader::<C>::chunk_size());
+
+    let reader = SignatureSliceReader::<C>::new(&buffer, SignatureSchemes::ProofOfPossession);
+    assert_eq!(reader.len(), 100);
+    let read = reader.collect::<BlsResult<Vec<_>>>().unwrap();
+    assert_eq!(read, sigs);
+
+    let truncated = &buffer[..buffer.len() - 1];
+    let mut reader = SignatureSliceReader::<C>::new(truncated, SignatureSchemes::ProofOfPossession);
+    for sig in sigs.iter().take(99) {
+        assert_eq!(reader.next().unwrap().unwrap(), *sig);
+    }
+    assert!(matches!(
+        reader.next(),
+        Some(Err(BlsError::InvalidInputs(_)))
+    ));
+    assert!(reader.next().is_none());
+
+    let mut invalid = buffer[..SignatureSliceReader::<C>::chunk_size()].to_vec();
+    invalid[1] ^= 0xff;
+    let mut reader = SignatureSliceReader::<C>::new(&invalid, SignatureSchemes::ProofOfPossession);
+    assert!(matches!(
+        reader.next(),
+        Some(Err(BlsError::InvalidSignature))
+    ));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn requires_distinct_messages() {
+    assert!(SignatureSchemes::Basic.requires_distinct_messages());
+    assert!(!SignatureSchemes::MessageAugmentation.requires_distinct_messages());
+    assert!(!SignatureSchemes::ProofOfPossession.requires_distinct_messages());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn sign_digest_is_domain_separated<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(
+    #[case] _c: C,
+) {
+    use sha2::{Digest, Sha256};
+
+    let sk = SecretKey::<C>::from_hash(b"sign_digest_is_domain_separated");
+    let pk = sk.public_key();
+    let digest: [u8; 32] = Sha256::digest(TEST_MSG).into();
+
+    for scheme in [
+        SignatureSchemes::Basic,
+        SignatureSchemes::MessageAugmentation,
+        SignatureSchemes::ProofOfPossession,
+    ] {
+        let sig = sk.sign_digest(scheme, &digest).unwrap();
+        assert!(sig.verify_digest(&pk, &digest).is_ok());
+        assert!(sig.verify_digest(&pk, &[0u8; 32]).is_err());
+
+        // Signing the digest as raw bytes is a different message
+        let raw = sk.sign(scheme, &digest).unwrap();
+        assert_ne!(sig, raw);
+        assert!(raw.verify_digest(&pk, &digest).is_err());
+        assert!(sig.verify(&pk, digest).is_err());
+
+        // and the only difference is the prefix
+        let prefixed = sk
+            .sign(scheme, &[&b"BLS_DIGEST32_"[..], &digest].concat())
+            .unwrap();
+        assert_eq!(sig, prefixed);
+    }
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn aggregate_verify_with_mapper<C: BlsSignatureImpl>(#[case] _c: C) {
+    let keys = [
+        SecretKey::<C>::new(),
+        SecretKey::<C>::new(),
+        SecretKey::<C>::new(),
+    ];
+    let msgs: [&[u8]; 3] = [b"mapper one", b"mapper two", TEST_MSG];
+
+    for scheme in [
+        SignatureSchemes::Basic,
+        SignatureSchemes::MessageAugmentation,
+        SignatureSchemes::ProofOfPossession,
+    ] {
+        let dst = match scheme {
+            SignatureSchemes::Basic => <C as BlsSignatureBasic>::DST,
+            SignatureSchemes::MessageAugmentation => <C as BlsSignatureMessageAugmentation>::DST,
+            SignatureSchemes::ProofOfPossession => <C as BlsSignaturePop>::SIG_DST,
+        };
+        let standard = |m: &[u8]| <C as HashToPoint>::hash_to_point(m, dst);
+
+        let sigs = keys
+            .iter()
+            .zip(msgs)
+            .map(|(sk, msg)| sk.sign(scheme, msg).unwrap())
+            .collect::<Vec<_>>();
+        let sig = AggregateSignature::from_signatures(&sigs).unwrap();
+        let mut data = keys
+            .iter()
+            .zip(msgs)
+            .map(|(sk, msg)| (sk.public_key(), msg))
+            .collect::<Vec<_>>();
+        assert!(sig.verify(&data).is_ok());
+        assert!(sig.verify_with_mapper(&data, standard).is_ok());
+
+        data[0].1 = BAD_MSG;
+        assert!(sig.verify(&data).is_err());
+        assert!(sig.verify_with_mapper(&data, standard).is_err());
+    }
+
+    // Signatures produced with a custom mapping only verify with that mapping
+    let custom = |m: &[u8]| <C as HashToPoint>::hash_to_point(m, b"MY_PROTOCOL_CUSTOM_MAPPING_");
+    let sigs = keys
+        .iter()
+        .zip(msgs)
+        .map(|(sk, msg)| Signature::<C>::ProofOfPossession(custom(msg) * sk.0))
+        .collect::<Vec<_>>();
+    let sig = AggregateSignature::from_signatures(&sigs).unwrap();
+    let data = keys
+        .iter()
+        .zip(msgs)
+        .map(|(sk, msg)| (sk.public_key(), msg))
+        .collect::<Vec<_>>();
+    assert!(sig.verify_with_mapper(&data, custom).is_ok());
+    assert!(sig.verify(&data).is_err());
+    assert!(matches!(
+        sig.verify_with_mapper(&data, |_| <C as Pairing>::Signature::identity()),
+        Err(BlsError::InvalidInputs(_))
+    ));
+    assert!(matches!(
+        sig.verify_with_mapper::<&[u8], _>(&[], custom),
+        Err(BlsError::EmptyAggregate)
+    ));
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn split_with_proof<C: BlsSignatureImpl + PartialEq + Eq + std::fmt::Debug>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let config = ThresholdConfig::new(3, 5).unwrap();
+    let (shares, proof) = sk
+        .split_with_proof(config, MockRng::from_seed([4u8; 16]))
+        .unwrap();
+    assert_eq!(shares.len(), 5);
+    assert_eq!(proof.threshold(), 3);
+    assert!(proof.verify(&pk).is_ok());
+    for share in &shares {
+        assert!(proof.verify_feldman(share).is_ok());
+    }
+    assert_eq!(SecretKey::combine(&shares[1..4]).unwrap(), sk);
+
+    let bytes = Vec::from(&proof);
+    assert_eq!(DealerProof::<C>::try_from(bytes.as_slice()).unwrap(), proof);
+
+    // A manipulated commitment vector is rejected
+    assert!(proof.verify(&SecretKey::<C>::new().public_key()).is_err());
+    let mut tampered = proof.clone();
+    tampered.commitments[1] = SecretKey::<C>::new().public_key();
+    assert!(tampered.verify(&pk).is_ok());
+    for share in &shares {
+        assert!(tampered.verify_feldman(share).is_err());
+    }
+    let mut tampered = proof.clone();
+    tampered.commitments[0] = SecretKey::<C>::new().public_key();
+    assert!(tampered.verify(&pk).is_err());
+    assert!(tampered.verify_feldman(&shares[0]).is_err());
+    let mut tampered = proof.clone();
+    tampered.commitments.truncate(1);
+    assert!(tampered.verify(&pk).is_err());
+
+    // A share from another dealing fails
+    let (other, _) = sk
+        .split_with_proof(config, MockRng::from_seed([5u8; 16]))
+        .unwrap();
+    assert!(proof.verify_feldman(&other[0]).is_err());
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn aggregate_verify_with_progress<C: BlsSignatureImpl>(#[case] _c: C) {
+    let keys = (0..5).map(|_| SecretKey::<C>::new()).collect::<Vec<_>>();
+    let msgs = (0..5u8).map(|i| vec![i; 8]).collect::<Vec<_>>();
+
+    for scheme in [
+        SignatureSchemes::Basic,
+        SignatureSchemes::MessageAugmentation,
+        SignatureSchemes::ProofOfPossession,
+    ] {
+        let sigs = keys
+            .iter()
+            .zip(&msgs)
+            .map(|(sk, msg)| sk.sign(scheme, msg).unwrap())
+            .collect::<Vec<_>>();
+        let sig = AggregateSignature::from_signatures(&sigs).unwrap();
+        let mut data = keys
+            .iter()
+            .zip(&msgs)
+            .map(|(sk, msg)| (sk.public_key(), msg.clone()))
+            .collect::<Vec<_>>();
+
+        let mut calls = Vec::new();
+        let res =
+            sig.verify_with_progress(&data, |processed, total| calls.push((processed, total)));
+        assert!(res.is_ok());
+        assert!(sig.verify(&data).is_ok());
+        assert_eq!(calls, (1..=5).map(|i| (i, 5)).collect::<Vec<_>>());
+
+        data[2].1 = BAD_MSG.to_vec();
+        let mut count = 0;
+        let res = sig.verify_with_progress(&data, |_, _| count += 1);
+        assert_eq!(count, 5);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            sig.verify(&data).unwrap_err().to_string()
+        );
+
+        data[2].1 = data[0].1.clone();
+        assert_eq!(
+            format!("{:?}", sig.verify_with_progress(&data, |_, _| {})),
+            format!("{:?}", sig.verify(&data))
+        );
+    }
+
+    let sig = AggregateSignature::<C>::default();
+    let res = sig.verify_with_progress::<&[u8], _>(&[], |_, _| panic!("no items"));
+    assert!(matches!(res, Err(BlsError::EmptyAggregate)));
+
+    let data = [(keys[0].public_key(), TEST_MSG)];
+    let res = sig.verify_with_progress(&data, |_, _| panic!("identity signature"));
+    assert!(matches!(res, Err(BlsError::InvalidSignature)));
+    assert!(matches!(sig.verify(&data), Err(BlsError::InvalidSignature)));
+
+    let sig = AggregateSignature::<C>::ProofOfPossession(<C as Pairing>::Signature::generator());
+    let data = [(
+        PublicKey::<C>(<C as Pairing>::PublicKey::identity()),
+        TEST_MSG,
+    )];
+    let res = sig.verify_with_progress(&data, |_, _| panic!("identity public key"));
+    assert!(matches!(res, Err(BlsError::InvalidPublicKey)));
+    assert!(matches!(sig.verify(&data), Err(BlsError::InvalidPublicKey)));
+}
+
+#[rstest]
+#[case::g1(Bls12381G1Impl)]
+#[case::g2(Bls12381G2Impl)]
+fn verify_any_signature<C: BlsSignatureImpl>(#[case] _c: C) {
+    let sk = SecretKey::<C>::new();
+    let pk = sk.public_key();
+    let other = SecretKey::<C>::new();
+
+    for scheme in [
+        SignatureSchemes::Basic,
+        SignatureSchemes::MessageAugmentation,
+        SignatureSchemes::ProofOfPossession,
+    ] {
+        let sigs = [
+            sk.sign(scheme, BAD_MSG).unwrap(),
+            other.sign(scheme, TEST_MSG).unwrap(),
+            sk.sign(scheme, TEST_MSG).unwrap(),
+            other.sign(scheme, BAD_MSG).unwrap(),
+            sk.sign(scheme, b"another message").unwrap(),
+        ];
+        assert_eq!(pk.verify_any_signature(TEST_MSG, &sigs, scheme), Some(2));
+        assert_eq!(pk.verify_any_signature(b"unsigned", &sigs, scheme), None);
+        assert_eq!(pk.verify_any_signature(TEST_MSG, &sigs[..2], scheme), None);
+        assert_eq!(pk.verify_any_signature(TEST_MSG, &[], scheme), None);
+    }
+
+    // A valid signature under a different scheme doesn't count
+    let sigs = [sk.sign(SignatureSchemes::Basic, TEST_MSG).unwrap()];
+    assert_eq!(
+        pk.verify_any_signature(TEST_MSG, &sigs, SignatureSchemes::ProofOfPossession),
+        None
+    );
+    assert_eq!(
+        pk.verify_any_signature(TEST_MSG, &sigs, SignatureSchemes::Basic),
+        Some(0)
+    );
+}
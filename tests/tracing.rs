@@ -0,0 +1,86 @@
+#![cfg(feature = "tracing")]
+
+mod utils;
+
+use blsful::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+use utils::*;
+
+type Fields = HashMap<&'static str, String>;
+
+/// Collects the name and fields of every span created while it is the default
+#[derive(Clone, Default)]
+struct SpanCollector {
+    spans: Arc<Mutex<Vec<(&'static str, Fields)>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for SpanCollector {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields::new();
+        span.record(&mut FieldVisitor(&mut fields));
+        let mut spans = self.spans.lock().unwrap();
+        spans.push((span.metadata().name(), fields));
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+        values.record(&mut FieldVisitor(fields));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn sign_and_verify_spans_record_scheme_and_outcome() {
+    let collector = SpanCollector::default();
+    let sk = SecretKey::<Bls12381G1Impl>::new();
+    let pk = sk.public_key();
+
+    tracing::subscriber::with_default(collector.clone(), || {
+        let sig = sk.sign(SignatureSchemes::Basic, TEST_MSG).unwrap();
+        assert!(sig.verify(&pk, TEST_MSG).is_ok());
+        assert!(sig.verify(&pk, b"wrong message").is_err());
+    });
+
+    let spans = collector.spans.lock().unwrap();
+    let expected = [
+        ("bls.sign", "ok"),
+        ("bls.verify", "ok"),
+        ("bls.verify", "error"),
+    ];
+    assert_eq!(spans.len(), expected.len());
+    for ((name, fields), (expected_name, expected_outcome)) in spans.iter().zip(expected) {
+        assert_eq!(*name, expected_name);
+        assert_eq!(fields["scheme"], "Basic");
+        assert_eq!(fields["outcome"], expected_outcome);
+        assert!(fields.contains_key("msg_len"));
+    }
+}
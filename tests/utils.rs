@@ -1,8 +1,10 @@
+#![allow(dead_code)]
+
 use rand_core::SeedableRng;
 
-pub const TEST_ID: &'static [u8] = b"super id";
-pub const TEST_MSG: &'static [u8] = b"signatures_work";
-pub const BAD_MSG: &'static [u8] = b"bad message";
+pub const TEST_ID: &[u8] = b"super id";
+pub const TEST_MSG: &[u8] = b"signatures_work";
+pub const BAD_MSG: &[u8] = b"bad message";
 
 pub struct MockRng(rand_xorshift::XorShiftRng);
 